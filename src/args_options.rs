@@ -1,4 +1,11 @@
-use clap::Parser;
+use std::{collections::BTreeMap, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+
+use crate::{
+    types::{canister_selection::CanisterSelection, settings::SettingsLayer},
+    DFX_NETWORK,
+};
 
 #[derive(Parser)]
 #[command(
@@ -9,8 +16,230 @@ use clap::Parser;
 )]
 pub struct Args {
     /// Specify one or more canister names to generate Candid files.
-    /// Each canister name should be provided as a separate argument.
+    /// Each canister name should be provided as a separate argument,
+    /// and can be a glob pattern such as 'ledger_*'.
     /// If no canister name is passed, this will generate
     /// the Candid files for every rust canister of the project.
     pub canisters_names: Option<Vec<String>>,
+
+    /// Skip the build and read the Candid interface from the
+    /// `candid:service` metadata section of the existing wasm files.
+    /// Falls back to executing `get_candid_pointer` when the section is missing.
+    #[arg(long)]
+    pub from_metadata: bool,
+
+    /// Skip the build and extract the Candid interfaces from the wasm files
+    /// that dfx left under `.dfx/<network>/canisters/<name>/` (`.wasm` or `.wasm.gz`).
+    #[arg(long, global = true)]
+    pub from_dfx_artifacts: bool,
+
+    /// dfx network of the artifacts read by `--from-dfx-artifacts`.
+    #[arg(long, global = true, default_value = DFX_NETWORK, requires = "from_dfx_artifacts")]
+    pub network: String,
+
+    /// Build and extract the Candid interfaces without writing them,
+    /// printing a diff for every outdated `.did` file.
+    /// Exits with an error if any of them is out of date.
+    #[arg(long)]
+    pub check: bool,
+
+    /// Refuse to overwrite a `.did` file when the new interface
+    /// is not a backward-compatible upgrade of the old one.
+    /// With `diff`, exits with an error if any breaking change is found.
+    #[arg(long, global = true)]
+    pub deny_breaking: bool,
+
+    /// Also generate the JavaScript/TypeScript declarations
+    /// (`<name>.did.js`, `<name>.did.d.ts` and `index.js`) after writing each `.did` file,
+    /// in the canister's `declarations.output` directory.
+    #[arg(long)]
+    pub declarations: bool,
+
+    /// Fail instead of skipping the `rust` canisters of `dfx.json`
+    /// that are not valid candid-gen canisters.
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// Path of the `.did` file of the rust canisters without a `candid` entry in `dfx.json`,
+    /// relative to the project root. `{name}` is replaced by the canister name
+    /// and `{package}` by its package name. Defaults to `src/{name}/{name}.did`.
+    #[arg(long, global = true)]
+    pub candid_path_template: Option<String>,
+
+    /// Write the derived `.did` paths back into `dfx.json`
+    /// as the `candid` entry of their canisters.
+    #[arg(long)]
+    pub write_dfx_json: bool,
+
+    /// Extract every canister again, even those whose wasm file and `.did` file
+    /// have not changed since the last run.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Leave out the canisters matching this name or glob pattern. Can be repeated.
+    #[arg(long, global = true, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Only select the canisters with this tag in the `tags` of their `candid_gen` settings.
+    /// Can be repeated to select the canisters with any of the tags.
+    #[arg(long = "tag", global = true, value_name = "TAG")]
+    pub tags: Vec<String>,
+
+    /// Find every `dfx.json` below the current directory (or `--project-root`)
+    /// and generate the Candid files of each of these projects.
+    #[arg(long, global = true)]
+    pub recursive: bool,
+
+    /// Root directory of the IC project. Defaults to the directory of `--dfx-json`,
+    /// or to the closest parent directory containing a `dfx.json`, up to the repository root.
+    #[arg(long, global = true)]
+    pub project_root: Option<PathBuf>,
+
+    /// `dfx.json` of the project. Defaults to the `dfx.json` of the project root.
+    #[arg(long, global = true)]
+    pub dfx_json: Option<PathBuf>,
+
+    /// `Cargo.toml` of the Cargo workspace of the canisters.
+    /// Defaults to the workspace found by `cargo locate-project --workspace` from the project root.
+    #[arg(long, global = true)]
+    pub manifest_path: Option<PathBuf>,
+
+    /// Cargo profile used to build the canisters. Defaults to `release`.
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    /// Comma separated list of features to activate when building the canisters,
+    /// in addition to the features of the configuration file and `dfx.json`.
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub features: Vec<String>,
+
+    /// Do not activate the `default` feature of the canisters.
+    #[arg(long, global = true)]
+    pub no_default_features: bool,
+
+    /// Pass `--locked` to cargo: require `Cargo.lock` to be up to date.
+    #[arg(long, global = true)]
+    pub locked: bool,
+
+    /// Pass `--offline` to cargo: run without accessing the network.
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Environment variable set for `cargo build`, as `KEY=VALUE` (e.g. `RUSTFLAGS=...`).
+    /// Can be repeated and overrides the variables of the configuration file and `dfx.json`.
+    #[arg(long = "env", global = true, value_name = "KEY=VALUE", value_parser = parse_env_var)]
+    pub env_vars: Vec<(String, String)>,
+
+    /// Number of canisters whose Candid interface is extracted concurrently.
+    /// Defaults to the number of available CPUs.
+    #[arg(short, long, global = true, value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: Option<u16>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+fn parse_env_var(env_var: &str) -> Result<(String, String), String> {
+    match env_var.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid KEY=VALUE: no `=` found in '{}'", env_var)),
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Compare the generated Candid interfaces with the `.did` files
+    /// as they exist at a git ref, both textually and for compatibility.
+    Diff {
+        /// Git ref (branch, tag or commit) to compare against.
+        #[arg(long)]
+        against: String,
+
+        /// Canisters to compare. Defaults to every rust canister of the project.
+        canisters_names: Option<Vec<String>>,
+    },
+    /// Extract the Candid interface of a single wasm file, without a dfx project.
+    Extract {
+        /// Wasm file to read, optionally gzipped (`.wasm.gz`).
+        #[arg(long)]
+        wasm: PathBuf,
+
+        /// `.did` file to write. Prints the Candid interface when omitted.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Inspect the settings merged from the command line, the `CANDID_GEN_*` variables,
+    /// `candid-gen.toml` (or `[workspace.metadata.candid-gen]`) and `dfx.json`.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Watch the sources of the canisters' packages and their path dependencies,
+    /// regenerating the Candid files of the affected canisters on every change.
+    Watch {
+        /// Canisters to watch. Defaults to every rust canister of the project.
+        canisters_names: Option<Vec<String>>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print the effective settings of each canister and where each value came from.
+    Explain {
+        /// Canisters to explain. Defaults to every rust canister of the project.
+        canisters_names: Option<Vec<String>>,
+    },
+}
+
+impl Args {
+    pub fn canisters_names(&self) -> &Option<Vec<String>> {
+        match &self.command {
+            Some(Command::Diff {
+                canisters_names, ..
+            })
+            | Some(Command::Watch { canisters_names })
+            | Some(Command::Config {
+                command: ConfigCommand::Explain { canisters_names },
+            }) => canisters_names,
+            Some(Command::Extract { .. }) | None => &self.canisters_names,
+        }
+    }
+
+    /// The canisters selected by name, pattern, `--exclude` and `--tag`.
+    pub fn canister_selection(&self) -> CanisterSelection {
+        CanisterSelection {
+            patterns: self.canisters_names().clone(),
+            exclude: self.exclude.clone(),
+            tags: self.tags.clone(),
+        }
+    }
+
+    /// Where the wasm files come from and how their candid is read, recorded in the
+    /// cache since the same wasm may yield a different interface in another mode.
+    pub fn extraction_mode(&self) -> &'static str {
+        match (self.from_dfx_artifacts, self.from_metadata) {
+            (false, false) => "build",
+            (false, true) => "metadata",
+            (true, false) => "dfx-artifacts",
+            (true, true) => "dfx-artifacts-metadata",
+        }
+    }
+
+    /// The settings set on the command line. Flags that are not passed are left unset,
+    /// so that they fall through to the other settings sources.
+    pub fn settings_layer(&self) -> SettingsLayer {
+        SettingsLayer {
+            candid_path_template: self.candid_path_template.clone(),
+            deny_breaking: self.deny_breaking.then_some(true),
+            strict: self.strict.then_some(true),
+            declarations: self.declarations.then_some(true),
+            profile: self.profile.clone(),
+            features: Some(self.features.clone()).filter(|features| !features.is_empty()),
+            no_default_features: self.no_default_features.then_some(true),
+            locked: self.locked.then_some(true),
+            offline: self.offline.then_some(true),
+            env: Some(self.env_vars.iter().cloned().collect::<BTreeMap<_, _>>())
+                .filter(|env| !env.is_empty()),
+        }
+    }
 }
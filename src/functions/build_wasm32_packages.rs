@@ -0,0 +1,164 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use cargo_metadata::{diagnostic::DiagnosticLevel, Message, Metadata};
+
+use crate::{
+    functions::{get_cargo_metadata::get_cargo_metadata, get_wasm_file_name::get_wasm_file_name},
+    types::{build_options::BuildOptions, dfx_cfg::RustCanisterCfg, error::CandidGenError},
+};
+
+#[derive(Debug, Default)]
+struct PackageBuild {
+    wasm_files: Vec<PathBuf>,
+    errors: Vec<String>,
+}
+
+pub fn build_wasm32_packages(
+    canisters: &[&RustCanisterCfg],
+    build_options: &BuildOptions,
+) -> BTreeMap<String, Result<String, CandidGenError>> {
+    let metadata = match get_cargo_metadata(build_options.manifest_path.as_deref()) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            return canisters
+                .iter()
+                .map(|canister| {
+                    (
+                        canister.package.clone(),
+                        Err(CandidGenError::Build {
+                            canister: canister.package.clone(),
+                            message: format!("{:#}", e),
+                        }),
+                    )
+                })
+                .collect()
+        }
+    };
+    let mut packages: Vec<&str> = canisters
+        .iter()
+        .map(|canister| canister.package.as_str())
+        .collect();
+    packages.sort();
+    packages.dedup();
+
+    let mut args = build_options.cargo_build_args(&packages);
+    args.push("--keep-going".to_string());
+    args.push("--message-format=json".to_string());
+    let package_builds = Command::new("cargo")
+        .args(args)
+        .envs(&build_options.env)
+        .stdout(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            let package_builds = read_build_messages(
+                BufReader::new(child.stdout.take().expect("piped stdout")),
+                &metadata,
+            );
+            child.wait()?;
+            Ok(package_builds)
+        });
+
+    canisters
+        .iter()
+        .map(|canister| {
+            let canister_name = &canister.package;
+            let to_build_error = |message: String| CandidGenError::Build {
+                canister: canister_name.clone(),
+                message,
+            };
+            let result = match &package_builds {
+                Err(e) => Err(to_build_error(format!("Failed to run cargo build: {}", e))),
+                Ok(package_builds) => {
+                    let package_build = package_builds.get(canister_name.as_str());
+                    let wasm_file_name = get_wasm_file_name(canister, Some(&metadata));
+                    let wasm_file = package_build.and_then(|package_build| {
+                        package_build.wasm_files.iter().find(|wasm_file| {
+                            wasm_file.file_name() == Some(wasm_file_name.as_ref())
+                        })
+                    });
+                    match (wasm_file, package_build) {
+                        (Some(wasm_file), _) => {
+                            let wasm_path_str = wasm_file.to_string_lossy().to_string();
+                            println!(
+                                "candid-gen: Canister '{}' built successfully at {}.",
+                                canister_name, wasm_path_str
+                            );
+                            Ok(wasm_path_str)
+                        }
+                        (None, Some(package_build)) if !package_build.errors.is_empty() => {
+                            Err(to_build_error(format!(
+                                "{} compilation error(s), the first one being: {}",
+                                package_build.errors.len(),
+                                package_build.errors[0]
+                            )))
+                        }
+                        (None, _) => Err(to_build_error(format!(
+                            "cargo build did not produce {}",
+                            wasm_file_name
+                        ))),
+                    }
+                }
+            };
+            (canister_name.clone(), result)
+        })
+        .collect()
+}
+
+fn read_build_messages(
+    reader: impl BufRead,
+    metadata: &Metadata,
+) -> BTreeMap<String, PackageBuild> {
+    let package_name = |package_id: &cargo_metadata::PackageId| {
+        metadata
+            .packages
+            .iter()
+            .find(|package| &package.id == package_id)
+            .map(|package| package.name.to_string())
+    };
+    let mut package_builds: BTreeMap<String, PackageBuild> = BTreeMap::new();
+    for message in Message::parse_stream(reader).map_while(Result::ok) {
+        match message {
+            Message::CompilerArtifact(artifact) => {
+                if let Some(package_name) = package_name(&artifact.package_id) {
+                    package_builds
+                        .entry(package_name)
+                        .or_default()
+                        .wasm_files
+                        .extend(
+                            artifact
+                                .filenames
+                                .into_iter()
+                                .filter(|filename| filename.extension() == Some("wasm"))
+                                .map(|filename| filename.into_std_path_buf()),
+                        );
+                }
+            }
+            Message::CompilerMessage(compiler_message) => {
+                let diagnostic = compiler_message.message;
+                if let Some(rendered) = &diagnostic.rendered {
+                    eprint!("{}", rendered);
+                }
+                if matches!(
+                    diagnostic.level,
+                    DiagnosticLevel::Error | DiagnosticLevel::Ice
+                ) {
+                    if let Some(package_name) = package_name(&compiler_message.package_id) {
+                        package_builds
+                            .entry(package_name)
+                            .or_default()
+                            .errors
+                            .push(diagnostic.message);
+                    }
+                }
+            }
+            Message::TextLine(line) => println!("{}", line),
+            _ => (),
+        }
+    }
+    package_builds
+}
@@ -0,0 +1,48 @@
+use std::{fs::read_to_string, path::Path};
+
+use anyhow::Result;
+
+use crate::{
+    functions::{
+        diff_candid::diff_candid,
+        print_message::{eprint_message, print_message},
+        read_wasm_candid::read_wasm_candid,
+    },
+    types::dfx_cfg::RustCanisterCfg,
+};
+
+pub fn check_candid(
+    project_root: &Path,
+    canister: &RustCanisterCfg,
+    wasm_path_str: &str,
+    from_metadata: bool,
+) -> Result<bool> {
+    let candid_file_path = project_root.join(&canister.candid_file_path_str);
+    let canister_name = &canister.package;
+
+    let candid = read_wasm_candid(wasm_path_str, from_metadata)?;
+    let current_candid = read_to_string(&candid_file_path).unwrap_or_default();
+    let candid_file_label = canister.candid_file_path_str.as_str();
+    match diff_candid(
+        &current_candid,
+        &candid,
+        candid_file_label,
+        &format!("{} (generated)", candid_file_label),
+    ) {
+        Some(diff) => {
+            print_message(diff.trim_end());
+            eprint_message(format!(
+                "candid-gen: Canister '{}' candid file {} is out of date.",
+                canister_name, candid_file_label
+            ));
+            Ok(false)
+        }
+        None => {
+            print_message(format!(
+                "candid-gen: Canister '{}' candid file is up to date.",
+                canister_name
+            ));
+            Ok(true)
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Context, Result};
+use candid::{
+    types::{
+        subtype::{subtype_check_all, Incompatibility},
+        Type,
+    },
+    TypeEnv,
+};
+
+use crate::functions::parse_candid::parse_candid;
+
+fn load_service(candid: &str, label: &str) -> Result<(TypeEnv, Type)> {
+    let (env, service) =
+        parse_candid(candid).with_context(|| format!("Invalid {} candid interface", label))?;
+    let service =
+        service.ok_or_else(|| anyhow!("The {} candid interface has no service", label))?;
+    Ok((env, service))
+}
+
+pub fn check_compatibility(old_candid: &str, new_candid: &str) -> Result<Vec<Incompatibility>> {
+    let (mut env, new_service) = load_service(new_candid, "new")?;
+    let (old_env, old_service) = load_service(old_candid, "old")?;
+    let old_service = env.merge_type(old_env, old_service);
+    Ok(subtype_check_all(
+        &mut HashSet::new(),
+        &env,
+        &new_service,
+        &old_service,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_CANDID: &str = r#"
+        type User = record { name : text; age : nat };
+        service : {
+            get_user : (nat) -> (User) query;
+            delete_user : (nat) -> ();
+        }
+    "#;
+
+    #[test]
+    fn test_check_compatibility_compatible() -> Result<()> {
+        let new_candid = r#"
+            type User = record { name : text; age : nat; email : opt text };
+            service : {
+                get_user : (nat) -> (User) query;
+                delete_user : (nat) -> ();
+                list_users : () -> (vec User) query;
+            }
+        "#;
+        assert!(check_compatibility(OLD_CANDID, new_candid)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_compatibility_breaking() -> Result<()> {
+        let new_candid = r#"
+            type User = record { name : text };
+            service : {
+                get_user : (nat) -> (User) query;
+            }
+        "#;
+        let breaking_changes = check_compatibility(OLD_CANDID, new_candid)?;
+        assert_eq!(breaking_changes.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_compatibility_invalid_candid() {
+        assert!(check_compatibility(OLD_CANDID, "service : {").is_err());
+    }
+}
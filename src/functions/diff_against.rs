@@ -0,0 +1,106 @@
+use std::{path::Path, process::Command};
+
+use anyhow::{anyhow, Result};
+use candid::types::subtype::format_report;
+
+use crate::{
+    functions::{
+        check_compatibility::check_compatibility,
+        diff_candid::diff_candid,
+        print_message::{eprint_message, print_message},
+        read_wasm_candid::read_wasm_candid,
+    },
+    types::dfx_cfg::RustCanisterCfg,
+};
+
+pub fn diff_against(
+    project_root: &Path,
+    canister: &RustCanisterCfg,
+    wasm_path_str: &str,
+    from_metadata: bool,
+    git_ref: &str,
+) -> Result<bool> {
+    let canister_name = &canister.package;
+    let candid_file_label = canister.candid_file_path_str.as_str();
+
+    let candid = read_wasm_candid(wasm_path_str, from_metadata)?;
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(project_root)
+            .output()
+    };
+    if !git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{}^{{commit}}", git_ref),
+    ])?
+    .status
+    .success()
+    {
+        return Err(anyhow!(
+            "'{}' is not a commit of the git repository",
+            git_ref
+        ));
+    }
+    let object = format!("{}:./{}", git_ref, candid_file_label);
+    if !git(&["cat-file", "-e", &object])?.status.success() {
+        print_message(format!(
+            "candid-gen: Canister '{}' candid file {} does not exist at '{}'.",
+            canister_name, candid_file_label, git_ref
+        ));
+        return Ok(true);
+    }
+    let output = git(&["show", &object])?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git show {} failed: {}",
+            object,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let old_candid = String::from_utf8_lossy(&output.stdout).to_string();
+
+    let Some(diff) = diff_candid(
+        &old_candid,
+        &candid,
+        &format!("{} ({})", candid_file_label, git_ref),
+        &format!("{} (generated)", candid_file_label),
+    ) else {
+        print_message(format!(
+            "candid-gen: Canister '{}' candid interface is unchanged since '{}'.",
+            canister_name, git_ref
+        ));
+        return Ok(true);
+    };
+    print_message(format!(
+        "candid-gen: Canister '{}' candid interface changed since '{}':",
+        canister_name, git_ref
+    ));
+    print_message(diff.trim_end());
+    match check_compatibility(&old_candid, &candid) {
+        Ok(breaking_changes) if breaking_changes.is_empty() => {
+            print_message(format!(
+                "candid-gen: Canister '{}' changes are backward compatible.",
+                canister_name
+            ));
+            Ok(true)
+        }
+        Ok(breaking_changes) => {
+            print_message(format!(
+                "candid-gen: Canister '{}' has breaking changes:\n{}",
+                canister_name,
+                format_report(&breaking_changes)
+            ));
+            Ok(false)
+        }
+        Err(e) => {
+            eprint_message(format!(
+                "candid-gen: Skipping the compatibility check of the canister '{}': {}",
+                canister_name, e
+            ));
+            Ok(true)
+        }
+    }
+}
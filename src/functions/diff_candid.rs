@@ -0,0 +1,32 @@
+use similar::TextDiff;
+
+pub fn diff_candid(old: &str, new: &str, old_label: &str, new_label: &str) -> Option<String> {
+    if old == new {
+        return None;
+    }
+    let diff = TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(old_label, new_label)
+        .to_string();
+    Some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_candid_equal() {
+        let candid = "service : { greet : (text) -> (text) query }\n";
+        assert_eq!(diff_candid(candid, candid, "a", "b"), None);
+    }
+
+    #[test]
+    fn test_diff_candid_changed() {
+        let old = "service : {\n  greet : (text) -> (text) query;\n}\n";
+        let new = "service : {\n  greet : (text) -> (text) query;\n  inc : () -> ();\n}\n";
+        let diff = diff_candid(old, new, "a.did", "b.did").unwrap();
+        assert!(diff.starts_with("--- a.did\n+++ b.did\n"));
+        assert!(diff.contains("+  inc : () -> ();\n"));
+    }
+}
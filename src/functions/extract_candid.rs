@@ -0,0 +1,54 @@
+use anyhow::{anyhow, Context, Result};
+use wasmi::{Engine, Error, Linker, Module, Store};
+
+pub fn extract_candid(wasm: &[u8]) -> Result<String> {
+    let engine = Engine::default();
+    let module = Module::new(&engine, wasm).context("Failed to load the wasm module")?;
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+
+    // Every imported function (the `ic0` system API) is stubbed with a trap:
+    // `get_candid_pointer` only reads static data and never calls into them.
+    for import in module.imports() {
+        if let Some(func_type) = import.ty().func() {
+            let import_name = format!("{}.{}", import.module(), import.name());
+            linker.func_new(
+                import.module(),
+                import.name(),
+                func_type.clone(),
+                move |_, _, _| {
+                    Err(Error::new(format!(
+                        "the imported function '{}' is not available during candid extraction",
+                        import_name
+                    )))
+                },
+            )?;
+        }
+    }
+
+    let instance = linker
+        .instantiate_and_start(&mut store, &module)
+        .context("Failed to instantiate the wasm module")?;
+    let get_candid_pointer = instance
+        .get_typed_func::<(), i32>(&store, "get_candid_pointer")
+        .context(
+            "The wasm module does not export 'get_candid_pointer'. \
+                Make sure the canister calls 'ic_cdk::export_candid!()'",
+        )?;
+    let candid_pointer = get_candid_pointer
+        .call(&mut store, ())
+        .context("Failed to call 'get_candid_pointer'")?;
+    let memory = instance
+        .get_memory(&store, "memory")
+        .ok_or_else(|| anyhow!("The wasm module does not export its memory"))?;
+
+    let memory_data = memory.data(&store);
+    let candid_start = candid_pointer as u32 as usize;
+    let candid_len = memory_data
+        .get(candid_start..)
+        .and_then(|data| data.iter().position(|byte| *byte == 0))
+        .ok_or_else(|| anyhow!("'get_candid_pointer' returned an invalid pointer"))?;
+    let candid = String::from_utf8(memory_data[candid_start..candid_start + candid_len].to_vec())
+        .context("The candid interface is not valid UTF-8")?;
+    Ok(candid)
+}
@@ -0,0 +1,31 @@
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target"];
+
+pub fn find_dfx_jsons(root: &Path) -> Vec<PathBuf> {
+    let mut dfx_paths = Vec::new();
+    let mut dirs_to_visit = vec![root.to_path_buf()];
+    while let Some(dir) = dirs_to_visit.pop() {
+        let dfx_path = dir.join("dfx.json");
+        if dfx_path.is_file() {
+            dfx_paths.push(dfx_path);
+        }
+        let Ok(entries) = read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir())
+                && !dir_name.starts_with('.')
+                && !SKIPPED_DIRS.contains(&dir_name.as_str())
+            {
+                dirs_to_visit.push(entry.path());
+            }
+        }
+    }
+    dfx_paths.sort();
+    dfx_paths
+}
@@ -1,38 +1,28 @@
-use std::{
-    path::{Path, PathBuf},
-    str::FromStr,
-};
+use std::path::Path;
 
-use anyhow::{anyhow, Result};
-use cmd_lib::run_cmd;
+use anyhow::Result;
 
 use crate::{
-    functions::get_candid_path_str::get_candid_path_str, types::dfx_cfg::RustCanisterCfg,
-    BUILD_OUTPUT_DIR,
+    functions::{
+        get_candid_path_str::get_candid_path_str, read_wasm_candid::read_wasm_candid,
+        update_candid::update_candid,
+    },
+    types::{dfx_cfg::RustCanisterCfg, error::CandidGenError},
 };
 
-pub fn gen_candid(project_root: &Path, canister: &RustCanisterCfg) -> Result<()> {
+pub fn gen_candid(
+    project_root: &Path,
+    canister: &RustCanisterCfg,
+    wasm_path_str: &str,
+    deny_breaking: bool,
+) -> Result<()> {
     let candid_path_str = get_candid_path_str(project_root, canister)?;
     let canister_name = &canister.package;
-    let canister_path_str = format!("{}/{}.wasm", BUILD_OUTPUT_DIR, canister_name);
-
-    if !PathBuf::from_str(&canister_path_str)?.is_file() {
-        return Err(anyhow!(
-            "Canister wasm file {} does not exists.",
-            canister_path_str
-        ));
-    }
-
-    if let Err(e) = run_cmd!(candid-extractor "$canister_path_str" > "$candid_path_str") {
-        eprintln!(
-            "Failed to extract candid for the canister '{}': {}",
-            canister_name, e
-        );
-    }
-    println!(
-        "candid-gen: Canister '{}' candid file was successfully generated.",
-        canister_name
-    );
 
-    Ok(())
+    let candid =
+        read_wasm_candid(wasm_path_str, false).map_err(|e| CandidGenError::Extraction {
+            canister: canister_name.clone(),
+            message: format!("{:#}", e),
+        })?;
+    update_candid(canister_name, &candid_path_str, &candid, deny_breaking)
 }
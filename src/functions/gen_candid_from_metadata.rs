@@ -0,0 +1,27 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::{
+    functions::{
+        get_candid_path_str::get_candid_path_str, read_wasm_candid::read_wasm_candid,
+        update_candid::update_candid,
+    },
+    types::{dfx_cfg::RustCanisterCfg, error::CandidGenError},
+};
+
+pub fn gen_candid_from_metadata(
+    project_root: &Path,
+    canister: &RustCanisterCfg,
+    wasm_path_str: &str,
+    deny_breaking: bool,
+) -> Result<()> {
+    let candid_path_str = get_candid_path_str(project_root, canister)?;
+    let canister_name = &canister.package;
+
+    let candid = read_wasm_candid(wasm_path_str, true).map_err(|e| CandidGenError::Extraction {
+        canister: canister_name.clone(),
+        message: format!("{:#}", e),
+    })?;
+    update_candid(canister_name, &candid_path_str, &candid, deny_breaking)
+}
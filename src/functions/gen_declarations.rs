@@ -0,0 +1,102 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use candid_parser::bindings::{javascript, typescript};
+
+use crate::{
+    functions::{parse_candid::parse_candid, print_message::print_message},
+    types::dfx_cfg::RustCanisterCfg,
+};
+
+pub const DECLARATIONS_DEFAULT_DIR: &str = "src/declarations";
+
+fn index_js(canister_name: &str, node_compatibility: bool) -> String {
+    let mut index_js = format!(
+        r#"import {{ Actor, HttpAgent }} from "@dfinity/agent";
+
+// Imports and re-exports candid interface
+import {{ idlFactory }} from "./{canister_name}.did.js";
+export {{ idlFactory }} from "./{canister_name}.did.js";
+
+export const canisterId =
+  process.env.CANISTER_ID_{canister_id_env};
+
+export const createActor = (canisterId, options = {{}}) => {{
+  const agent = options.agent || new HttpAgent({{ ...options.agentOptions }});
+
+  if (options.agent && options.agentOptions) {{
+    console.warn(
+      "Detected both agent and agentOptions passed to createActor. Ignoring agentOptions and proceeding with the provided agent."
+    );
+  }}
+
+  // Fetch root key for certificate validation during development
+  if (process.env.DFX_NETWORK !== "ic") {{
+    agent.fetchRootKey().catch((err) => {{
+      console.warn(
+        "Unable to fetch root key. Check to ensure that your local replica is running"
+      );
+      console.error(err);
+    }});
+  }}
+
+  // Creates an actor with using the candid interface and the HttpAgent
+  return Actor.createActor(idlFactory, {{
+    agent,
+    canisterId,
+    ...options.actorOptions,
+  }});
+}};
+"#,
+        canister_name = canister_name,
+        canister_id_env = canister_name.to_uppercase().replace('-', "_"),
+    );
+    if !node_compatibility {
+        index_js.push_str(&format!(
+            "\nexport const {} = canisterId ? createActor(canisterId) : undefined;\n",
+            canister_name.replace('-', "_")
+        ));
+    }
+    index_js
+}
+
+pub fn gen_declarations(
+    project_root: &Path,
+    canister_name: &str,
+    canister: &RustCanisterCfg,
+) -> Result<()> {
+    let candid_file_path = project_root.join(&canister.candid_file_path_str);
+    let candid = read_to_string(&candid_file_path)
+        .with_context(|| format!("Failed to read {}", candid_file_path.display()))?;
+    let (env, actor) = parse_candid(&candid)?;
+    let declarations_dir = match canister.declarations_output() {
+        Some(output) => project_root.join(output),
+        None => project_root
+            .join(DECLARATIONS_DEFAULT_DIR)
+            .join(canister_name),
+    };
+    create_dir_all(&declarations_dir)?;
+
+    write(
+        declarations_dir.join(format!("{}.did.js", canister_name)),
+        javascript::compile(&env, &actor),
+    )?;
+    write(
+        declarations_dir.join(format!("{}.did.d.ts", canister_name)),
+        typescript::compile(&env, &actor),
+    )?;
+    write(
+        declarations_dir.join("index.js"),
+        index_js(canister_name, canister.declarations_node_compatibility()),
+    )?;
+    print_message(format!(
+        "candid-gen: Canister '{}' declarations were successfully generated at {}.",
+        canister_name,
+        declarations_dir.display()
+    ));
+
+    Ok(())
+}
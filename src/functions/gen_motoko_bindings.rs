@@ -0,0 +1,36 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use candid_parser::bindings::motoko::compile;
+
+use crate::{
+    functions::{parse_candid::parse_candid, print_message::print_message},
+    types::dfx_cfg::RustCanisterCfg,
+};
+
+pub fn gen_motoko_bindings(
+    project_root: &Path,
+    canister_name: &str,
+    canister: &RustCanisterCfg,
+    bindings_dir: &Path,
+) -> Result<()> {
+    let candid_file_path = project_root.join(&canister.candid_file_path_str);
+    let candid = read_to_string(&candid_file_path)
+        .with_context(|| format!("Failed to read {}", candid_file_path.display()))?;
+    let (env, actor) = parse_candid(&candid)?;
+
+    let bindings_dir = project_root.join(bindings_dir);
+    create_dir_all(&bindings_dir)?;
+    let bindings_path = bindings_dir.join(format!("{}.mo", canister_name));
+    write(&bindings_path, compile(&env, &actor))?;
+    print_message(format!(
+        "candid-gen: Motoko bindings for the canister '{}' were successfully generated at {}.",
+        canister_name,
+        bindings_path.display()
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use candid_parser::bindings::rust::{compile, Config, Target};
+
+use crate::{
+    functions::{parse_candid::parse_candid, print_message::print_message},
+    types::dfx_cfg::RustCanisterCfg,
+};
+
+pub fn gen_rust_bindings(
+    project_root: &Path,
+    dependency_name: &str,
+    dependency: &RustCanisterCfg,
+    bindings_dir: &Path,
+) -> Result<()> {
+    let candid_file_path = project_root.join(&dependency.candid_file_path_str);
+    let candid = read_to_string(&candid_file_path)
+        .with_context(|| format!("Failed to read {}", candid_file_path.display()))?;
+    let (env, actor) = parse_candid(&candid)?;
+    let module_name = dependency_name.replace('-', "_");
+    let mut config = Config::new();
+    config
+        .set_target(Target::CanisterCall)
+        .set_service_name(module_name.clone());
+
+    let bindings_dir = project_root.join(bindings_dir);
+    create_dir_all(&bindings_dir)?;
+    let bindings_path = bindings_dir.join(format!("{}.rs", module_name));
+    let bindings = compile(&config, &env, &actor);
+    // Leave unchanged bindings untouched: they live in the sources watched by `candid-gen watch`.
+    if read_to_string(&bindings_path).is_ok_and(|current_bindings| current_bindings == bindings) {
+        print_message(format!(
+            "candid-gen: Rust bindings for the canister '{}' are up to date.",
+            dependency_name
+        ));
+        return Ok(());
+    }
+    write(&bindings_path, bindings)?;
+    print_message(format!(
+        "candid-gen: Rust bindings for the canister '{}' were successfully generated at {}.",
+        dependency_name,
+        bindings_path.display()
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use std::path::PathBuf;
+
+use cargo_metadata::Metadata;
+
+use crate::BUILD_TARGET;
+
+pub fn get_build_output_dir(metadata: &Metadata, profile_dir: &str) -> PathBuf {
+    metadata
+        .target_directory
+        .as_std_path()
+        .join(BUILD_TARGET)
+        .join(profile_dir)
+}
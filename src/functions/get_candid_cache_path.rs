@@ -0,0 +1,11 @@
+use std::path::PathBuf;
+
+use cargo_metadata::Metadata;
+
+pub fn get_candid_cache_path(metadata: &Metadata) -> PathBuf {
+    metadata
+        .target_directory
+        .as_std_path()
+        .join("candid-gen")
+        .join("cache.json")
+}
@@ -5,18 +5,15 @@ use anyhow::{anyhow, Result};
 use crate::types::dfx_cfg::RustCanisterCfg;
 
 pub fn get_candid_path_str(project_root: &Path, canister: &RustCanisterCfg) -> Result<String> {
-    let candid_file_path = project_root.join(&canister.candid_file_path_str);
-    let candid_file_dir = candid_file_path.parent().unwrap();
-    create_dir_all(candid_file_dir)?;
-    if !candid_file_dir
-        .to_str()
-        .unwrap()
-        .contains(&canister.package)
-    {
+    if canister.candid_file_path_str.is_empty() {
         return Err(anyhow!(
-            "fn gen_candid_path_str: Could not find the candid dir."
+            "fn get_candid_path_str: The candid path of the canister '{}' is empty.",
+            canister.package
         ));
     }
+    let candid_file_path = project_root.join(&canister.candid_file_path_str);
+    let candid_file_dir = candid_file_path.parent().unwrap();
+    create_dir_all(candid_file_dir)?;
     let candid_file_path_str = candid_file_path.to_str().unwrap().to_string();
     Ok(candid_file_path_str)
 }
@@ -51,7 +48,7 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_get_candid_path_str_failure_invalid_path() {
+    fn test_get_candid_path_str_failure_empty_path() {
         let temp_dir = tempdir().expect("Failed to create temp dir");
         set_current_dir(&temp_dir)
             .expect("Failed to set temp_dir as the current dir and project_root.");
@@ -65,7 +62,7 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
-            "fn gen_candid_path_str: Could not find the candid dir."
+            "fn get_candid_path_str: The candid path of the canister 'test' is empty."
         );
     }
 
@@ -95,4 +92,24 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_get_candid_path_str_shared_directory() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        set_current_dir(&temp_dir)
+            .expect("Failed to set temp_dir as the current dir and project_root.");
+        let canister = RustCanisterCfg {
+            package: "test".to_string(),
+            candid_file_path_str: "candid/wallet.did".to_string(),
+            other: HashMap::new(),
+        };
+
+        let result = get_candid_path_str(temp_dir.path(), &canister);
+        assert_eq!(
+            result.unwrap(),
+            temp_dir.path().join("candid/wallet.did").to_str().unwrap()
+        );
+        assert!(temp_dir.path().join("candid").is_dir());
+    }
 }
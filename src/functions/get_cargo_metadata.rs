@@ -0,0 +1,15 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use cargo_metadata::{Metadata, MetadataCommand};
+
+pub fn get_cargo_metadata(manifest_path: Option<&Path>) -> Result<Metadata> {
+    let mut metadata_command = MetadataCommand::new();
+    if let Some(manifest_path) = manifest_path {
+        metadata_command.manifest_path(manifest_path);
+    }
+    metadata_command
+        .no_deps()
+        .exec()
+        .context("Failed to read the cargo metadata")
+}
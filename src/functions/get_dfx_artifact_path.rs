@@ -0,0 +1,25 @@
+use std::path::{Path, PathBuf};
+
+use crate::DFX_ARTIFACTS_DIR;
+
+pub fn get_dfx_artifact_path(project_root: &Path, canister_name: &str, network: &str) -> PathBuf {
+    let artifact_dir = project_root
+        .join(DFX_ARTIFACTS_DIR)
+        .join(network)
+        .join("canisters")
+        .join(canister_name);
+    let wasm_path = artifact_dir.join(format!("{}.wasm", canister_name));
+    let wasm_gz_path = artifact_dir.join(format!("{}.wasm.gz", canister_name));
+    let modified = |path: &Path| {
+        path.metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    };
+    match (modified(&wasm_path), modified(&wasm_gz_path)) {
+        (Some(wasm_modified), Some(wasm_gz_modified)) if wasm_gz_modified > wasm_modified => {
+            wasm_gz_path
+        }
+        (None, Some(_)) => wasm_gz_path,
+        _ => wasm_path,
+    }
+}
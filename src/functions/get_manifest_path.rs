@@ -0,0 +1,24 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{anyhow, Context, Result};
+
+pub fn get_manifest_path(project_root: &Path) -> Result<PathBuf> {
+    let output = Command::new("cargo")
+        .args(["locate-project", "--workspace", "--message-format", "plain"])
+        .current_dir(project_root)
+        .output()
+        .context("Failed to run cargo locate-project")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Failed to find the Cargo workspace of {}: {}. Use --manifest-path to set it",
+            project_root.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim_end(),
+    ))
+}
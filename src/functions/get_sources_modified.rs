@@ -0,0 +1,33 @@
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::functions::get_watch_paths::is_source_file;
+
+/// Latest modification time of the Rust sources and manifests under `paths`.
+pub fn get_sources_modified(paths: &[PathBuf], target_dir: &Path) -> Option<SystemTime> {
+    let mut sources_modified = None;
+    let mut dirs_to_visit: Vec<PathBuf> = paths.to_vec();
+    while let Some(dir) = dirs_to_visit.pop() {
+        let Ok(entries) = read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if metadata.is_dir() {
+                if !is_hidden && entry.file_name() != "node_modules" && path != target_dir {
+                    dirs_to_visit.push(path);
+                }
+            } else if is_source_file(&path, target_dir) {
+                sources_modified = sources_modified.max(metadata.modified().ok());
+            }
+        }
+    }
+    sources_modified
+}
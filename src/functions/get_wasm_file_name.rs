@@ -0,0 +1,25 @@
+use cargo_metadata::Metadata;
+
+use crate::types::dfx_cfg::RustCanisterCfg;
+
+pub fn get_wasm_file_name(canister: &RustCanisterCfg, metadata: Option<&Metadata>) -> String {
+    let lib_name = match canister.crate_name() {
+        Some(crate_name) => crate_name.to_string(),
+        None => metadata
+            .and_then(|metadata| {
+                metadata
+                    .packages
+                    .iter()
+                    .find(|package| package.name.as_str() == canister.package)
+            })
+            .and_then(|package| {
+                package
+                    .targets
+                    .iter()
+                    .find(|target| target.is_cdylib() || target.is_lib())
+            })
+            .map(|target| target.name.clone())
+            .unwrap_or_else(|| canister.package.clone()),
+    };
+    format!("{}.wasm", lib_name.replace('-', "_"))
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use cargo_metadata::Metadata;
+
+use crate::{
+    functions::{
+        get_build_output_dir::get_build_output_dir, get_wasm_file_name::get_wasm_file_name,
+    },
+    types::{build_options::BuildOptions, dfx_cfg::RustCanisterCfg, error::CandidGenError},
+};
+
+pub fn get_wasm_path_str(
+    canister: &RustCanisterCfg,
+    build_options: &BuildOptions,
+    metadata: &Metadata,
+) -> Result<String> {
+    let canister_name = &canister.package;
+    if let Some(dfx_artifact) = &build_options.dfx_artifact {
+        let dfx_artifact_str = dfx_artifact.to_string_lossy().to_string();
+        if !dfx_artifact.is_file() {
+            return Err(CandidGenError::Extraction {
+                canister: canister_name.clone(),
+                message: format!(
+                    "dfx artifact {} does not exist. Run 'dfx build' first.",
+                    dfx_artifact_str
+                ),
+            }
+            .into());
+        }
+        return Ok(dfx_artifact_str);
+    }
+    let canister_path = get_build_output_dir(metadata, build_options.profile_dir())
+        .join(get_wasm_file_name(canister, Some(metadata)));
+    let canister_path_str = canister_path.to_string_lossy().to_string();
+
+    if !canister_path.is_file() {
+        return Err(CandidGenError::Extraction {
+            canister: canister_name.clone(),
+            message: format!("Canister wasm file {} does not exists.", canister_path_str),
+        }
+        .into());
+    }
+    Ok(canister_path_str)
+}
@@ -0,0 +1,52 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use cargo_metadata::Metadata;
+
+pub fn get_watch_paths(package_name: &str, metadata: &Metadata) -> Vec<PathBuf> {
+    let mut watch_paths = BTreeSet::new();
+    let mut visited_packages = BTreeSet::new();
+    let mut packages_to_visit = vec![package_name];
+    while let Some(package_name) = packages_to_visit.pop() {
+        if !visited_packages.insert(package_name) {
+            continue;
+        }
+        let Some(package) = metadata
+            .packages
+            .iter()
+            .find(|package| package.name.as_str() == package_name)
+        else {
+            continue;
+        };
+        if let Some(package_dir) = package.manifest_path.parent() {
+            watch_paths.insert(package_dir.as_std_path().to_path_buf());
+        }
+        for dependency in package.dependencies.iter() {
+            let Some(dependency_path) = &dependency.path else {
+                continue;
+            };
+            match metadata
+                .packages
+                .iter()
+                .any(|package| package.name.as_str() == dependency.name)
+            {
+                true => packages_to_visit.push(&dependency.name),
+                false => {
+                    watch_paths.insert(dependency_path.as_std_path().to_path_buf());
+                }
+            }
+        }
+    }
+    watch_paths.into_iter().collect()
+}
+
+/// Whether a change to `path` can change a wasm: Rust sources and manifests outside the target directory.
+pub fn is_source_file(path: &Path, target_dir: &Path) -> bool {
+    !path.starts_with(target_dir)
+        && (path.extension().is_some_and(|extension| extension == "rs")
+            || path
+                .file_name()
+                .is_some_and(|file_name| file_name == "Cargo.toml"))
+}
@@ -1,5 +1,34 @@
-pub mod build_wasm32;
+pub mod build_wasm32_packages;
+pub mod check_candid;
+pub mod check_compatibility;
+pub mod diff_against;
+pub mod diff_candid;
+pub mod extract_candid;
+pub mod find_dfx_jsons;
 pub mod gen_candid;
+pub mod gen_candid_from_metadata;
+pub mod gen_declarations;
+pub mod gen_motoko_bindings;
+pub mod gen_rust_bindings;
+pub mod get_build_output_dir;
+pub mod get_candid_cache_path;
 pub mod get_candid_path_str;
+pub mod get_cargo_metadata;
+pub mod get_dfx_artifact_path;
+pub mod get_manifest_path;
 pub mod get_project_root;
+pub mod get_sources_modified;
+pub mod get_wasm_file_name;
+pub mod get_wasm_path_str;
+pub mod get_watch_paths;
+pub mod parse_candid;
+pub mod post_process_candid;
+pub mod print_message;
+pub mod read_candid_metadata;
+pub mod read_settings_file;
+pub mod read_wasm;
+pub mod read_wasm_candid;
 pub mod run_command;
+pub mod update_candid;
+pub mod write_candid;
+pub mod write_dfx_candid_paths;
@@ -0,0 +1,13 @@
+use anyhow::{anyhow, Result};
+use candid::{types::Type, TypeEnv};
+use candid_parser::{check_prog, IDLProg};
+
+pub fn parse_candid(candid: &str) -> Result<(TypeEnv, Option<Type>)> {
+    let prog: IDLProg = candid
+        .parse()
+        .map_err(|e| anyhow!("Failed to parse the candid interface: {}", e))?;
+    let mut env = TypeEnv::new();
+    let actor = check_prog(&mut env, &prog)
+        .map_err(|e| anyhow!("Failed to type check the candid interface: {}", e))?;
+    Ok((env, actor))
+}
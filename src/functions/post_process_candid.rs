@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    functions::{print_message::print_message, run_command::run_command},
+    types::dfx_cfg::RustCanisterCfg,
+};
+
+pub fn post_process_candid(
+    project_root: &Path,
+    canister_name: &str,
+    canister: &RustCanisterCfg,
+    commands: &[String],
+) -> Result<()> {
+    let candid_path = project_root.join(&canister.candid_file_path_str);
+    for command in commands {
+        let command = command
+            .replace("{path}", &candid_path.to_string_lossy())
+            .replace("{name}", canister_name);
+        run_command(&command)
+            .with_context(|| format!("Post-processing command failed: {}", command))?;
+        print_message(format!(
+            "candid-gen: Canister '{}' candid file was post-processed with '{}'.",
+            canister_name, command
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::cell::RefCell;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CapturedMessage {
+    Stdout(String),
+    Stderr(String),
+}
+
+thread_local! {
+    static CAPTURED_MESSAGES: RefCell<Option<Vec<CapturedMessage>>> = const { RefCell::new(None) };
+}
+
+fn print_or_capture(message: CapturedMessage) {
+    let message = CAPTURED_MESSAGES.with_borrow_mut(|captured_messages| match captured_messages {
+        Some(captured_messages) => {
+            captured_messages.push(message);
+            None
+        }
+        None => Some(message),
+    });
+    match message {
+        Some(CapturedMessage::Stdout(message)) => println!("{}", message),
+        Some(CapturedMessage::Stderr(message)) => eprintln!("{}", message),
+        None => (),
+    }
+}
+
+pub fn print_message(message: impl Into<String>) {
+    print_or_capture(CapturedMessage::Stdout(message.into()));
+}
+
+pub fn eprint_message(message: impl Into<String>) {
+    print_or_capture(CapturedMessage::Stderr(message.into()));
+}
+
+/// Runs `f`, collecting the messages it prints on this thread instead of printing them,
+/// so that the output of concurrent canisters does not interleave.
+pub fn capture_messages<T>(f: impl FnOnce() -> T) -> (T, Vec<CapturedMessage>) {
+    let previous = CAPTURED_MESSAGES
+        .with_borrow_mut(|captured_messages| captured_messages.replace(Vec::new()));
+    let result = f();
+    let captured_messages = CAPTURED_MESSAGES
+        .with_borrow_mut(|captured_messages| std::mem::replace(captured_messages, previous));
+    (result, captured_messages.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_messages() {
+        let (result, captured_messages) = capture_messages(|| {
+            print_message("generated");
+            eprint_message(String::from("warning"));
+            42
+        });
+        assert_eq!(result, 42);
+        assert_eq!(
+            captured_messages,
+            vec![
+                CapturedMessage::Stdout("generated".to_string()),
+                CapturedMessage::Stderr("warning".to_string())
+            ]
+        );
+        let (_, captured_messages) = capture_messages(|| ());
+        assert!(captured_messages.is_empty());
+    }
+}
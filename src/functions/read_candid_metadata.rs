@@ -0,0 +1,20 @@
+use anyhow::{Context, Result};
+use wasmi::{Engine, Module};
+
+pub const CANDID_SERVICE_SECTIONS: &[&str] =
+    &["icp:public candid:service", "icp:private candid:service"];
+
+pub fn read_candid_metadata(wasm: &[u8]) -> Result<Option<String>> {
+    let module = Module::new(&Engine::default(), wasm).context("Failed to load the wasm module")?;
+    let section = module
+        .custom_sections()
+        .find(|section| CANDID_SERVICE_SECTIONS.contains(&section.name()));
+    match section {
+        Some(section) => {
+            let candid = String::from_utf8(section.data().to_vec())
+                .with_context(|| format!("The '{}' metadata is not valid UTF-8", section.name()))?;
+            Ok(Some(candid))
+        }
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,46 @@
+use std::{fs::read_to_string, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    types::settings::{SettingSource, SettingsLayer},
+    SETTINGS_FILE,
+};
+
+/// Reads the `candid-gen.toml` of the project root or, when there is none,
+/// the `[workspace.metadata.candid-gen]` table of the Cargo manifest.
+pub fn read_settings_file(
+    project_root: &Path,
+    manifest_path: &Path,
+) -> Result<Option<(SettingSource, SettingsLayer)>> {
+    let settings_path = project_root.join(SETTINGS_FILE);
+    if settings_path.is_file() {
+        let settings_toml = read_to_string(&settings_path)
+            .with_context(|| format!("Failed to read {}", settings_path.display()))?;
+        let settings_layer: SettingsLayer = toml::from_str(&settings_toml)
+            .with_context(|| format!("Invalid {}", settings_path.display()))?;
+        return Ok(Some((SettingSource::File(settings_path), settings_layer)));
+    }
+
+    let manifest_toml = read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let manifest: toml::Table = toml::from_str(&manifest_toml)
+        .with_context(|| format!("Invalid {}", manifest_path.display()))?;
+    let Some(settings_table) = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("metadata"))
+        .and_then(|metadata| metadata.get("candid-gen"))
+    else {
+        return Ok(None);
+    };
+    let settings_layer: SettingsLayer = settings_table.clone().try_into().with_context(|| {
+        format!(
+            "Invalid [workspace.metadata.candid-gen] in {}",
+            manifest_path.display()
+        )
+    })?;
+    Ok(Some((
+        SettingSource::CargoManifest(manifest_path.to_path_buf()),
+        settings_layer,
+    )))
+}
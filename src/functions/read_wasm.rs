@@ -0,0 +1,20 @@
+use std::{fs::read, io::Read, path::Path};
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+pub fn read_wasm(wasm_path: impl AsRef<Path>) -> Result<Vec<u8>> {
+    let wasm_path = wasm_path.as_ref();
+    let wasm =
+        read(wasm_path).with_context(|| format!("Failed to read {}", wasm_path.display()))?;
+    if !wasm.starts_with(GZIP_MAGIC) {
+        return Ok(wasm);
+    }
+    let mut decompressed_wasm = Vec::new();
+    GzDecoder::new(wasm.as_slice())
+        .read_to_end(&mut decompressed_wasm)
+        .with_context(|| format!("Failed to decompress {}", wasm_path.display()))?;
+    Ok(decompressed_wasm)
+}
@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::functions::{
+    extract_candid::extract_candid, print_message::print_message,
+    read_candid_metadata::read_candid_metadata, read_wasm::read_wasm,
+};
+
+/// Reads the candid interface of a wasm file, optionally gzipped. The module is executed
+/// through `get_candid_pointer`, unless `from_metadata` is set: its `candid:service` metadata
+/// section is then read first, executing `get_candid_pointer` only when the section is missing.
+pub fn read_wasm_candid(wasm_path: impl AsRef<Path>, from_metadata: bool) -> Result<String> {
+    let wasm_path = wasm_path.as_ref();
+    let wasm = read_wasm(wasm_path)?;
+    if from_metadata {
+        if let Some(candid) = read_candid_metadata(&wasm)? {
+            return Ok(candid);
+        }
+        print_message(format!(
+            "candid-gen: {} has no candid:service metadata, executing 'get_candid_pointer'.",
+            wasm_path.display()
+        ));
+    }
+    extract_candid(&wasm)
+}
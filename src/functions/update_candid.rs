@@ -0,0 +1,53 @@
+use std::fs::read_to_string;
+
+use anyhow::Result;
+use candid::types::subtype::format_report;
+
+use crate::{
+    functions::{
+        check_compatibility::check_compatibility,
+        print_message::{eprint_message, print_message},
+        write_candid::write_candid,
+    },
+    types::error::CandidGenError,
+};
+
+/// Writes the generated candid of a canister over its `.did` file, reporting the changes
+/// that break the clients of the existing interface. With `deny_breaking`, such changes
+/// fail the canister and the `.did` file is left untouched.
+pub fn update_candid(
+    canister_name: &str,
+    candid_path_str: &str,
+    candid: &str,
+    deny_breaking: bool,
+) -> Result<()> {
+    if let Ok(old_candid) = read_to_string(candid_path_str) {
+        match check_compatibility(&old_candid, candid) {
+            Ok(breaking_changes) if !breaking_changes.is_empty() => {
+                eprint_message(format!(
+                    "candid-gen: Canister '{}' has breaking changes in its candid interface:\n{}",
+                    canister_name,
+                    format_report(&breaking_changes)
+                ));
+                if deny_breaking {
+                    return Err(CandidGenError::Breaking {
+                        canister: canister_name.to_string(),
+                    }
+                    .into());
+                }
+            }
+            Ok(_) => (),
+            Err(e) => eprint_message(format!(
+                "candid-gen: Skipping the compatibility check of the canister '{}': {}",
+                canister_name, e
+            )),
+        }
+    }
+    write_candid(candid_path_str, candid)?;
+    print_message(format!(
+        "candid-gen: Canister '{}' candid file was successfully generated.",
+        canister_name
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use std::fs::write;
+
+use crate::types::error::CandidGenError;
+
+pub fn write_candid(candid_path_str: &str, candid: &str) -> Result<(), CandidGenError> {
+    write(candid_path_str, candid).map_err(|e| CandidGenError::Write {
+        path: candid_path_str.to_string(),
+        message: e.to_string(),
+    })
+}
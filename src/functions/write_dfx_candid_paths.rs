@@ -0,0 +1,158 @@
+use std::{
+    fs::{read_to_string, write},
+    path::Path,
+};
+
+use anyhow::{anyhow, Context, Result};
+
+fn skip_whitespace(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+        index += 1;
+    }
+    index
+}
+
+fn find_object_start(json: &str, path: &[&str]) -> Option<usize> {
+    let bytes = json.as_bytes();
+    let mut depth = 0;
+    let mut matched = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' => {
+                let start = index + 1;
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    if bytes[index] == b'\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+                let key = &json[start..index.min(json.len())];
+                let after_key = skip_whitespace(bytes, index + 1);
+                if depth == matched + 1
+                    && matched < path.len()
+                    && key == path[matched]
+                    && bytes.get(after_key) == Some(&b':')
+                {
+                    let value_start = skip_whitespace(bytes, after_key + 1);
+                    if bytes.get(value_start) == Some(&b'{') {
+                        matched += 1;
+                        if matched == path.len() {
+                            return Some(value_start);
+                        }
+                        depth += 1;
+                        index = value_start;
+                    }
+                }
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                if depth == matched + 1 && matched > 0 {
+                    return None;
+                }
+                depth -= 1;
+            }
+            _ => (),
+        }
+        index += 1;
+    }
+    None
+}
+
+pub fn insert_candid_path(
+    dfx_json: &str,
+    canister_name: &str,
+    candid_path: &str,
+) -> Result<String> {
+    let object_start = find_object_start(dfx_json, &["canisters", canister_name])
+        .ok_or_else(|| anyhow!("Could not find the canister '{}'", canister_name))?;
+    let bytes = dfx_json.as_bytes();
+    let first_member = skip_whitespace(bytes, object_start + 1);
+    let candid_member = format!("\"candid\": {}", serde_json::to_string(candid_path)?);
+    let indentation = &dfx_json[object_start + 1..first_member];
+    let (position, insertion) = if bytes.get(first_member) == Some(&b'}') {
+        (object_start + 1, candid_member)
+    } else if indentation.contains('\n') {
+        (
+            object_start + 1,
+            format!("{}{},", indentation, candid_member),
+        )
+    } else {
+        (first_member, format!("{}, ", candid_member))
+    };
+    let mut dfx_json = dfx_json.to_string();
+    dfx_json.insert_str(position, &insertion);
+    Ok(dfx_json)
+}
+
+pub fn write_dfx_candid_paths(dfx_path: &Path, candid_paths: &[(String, String)]) -> Result<()> {
+    let mut dfx_json = read_to_string(dfx_path)
+        .with_context(|| format!("Failed to read {}", dfx_path.display()))?;
+    for (canister_name, candid_path) in candid_paths {
+        dfx_json = insert_candid_path(&dfx_json, canister_name, candid_path)
+            .with_context(|| format!("Failed to update {}", dfx_path.display()))?;
+        println!(
+            "candid-gen: Canister '{}' candid path {} was written to {}.",
+            canister_name,
+            candid_path,
+            dfx_path.display()
+        );
+    }
+    write(dfx_path, dfx_json).with_context(|| format!("Failed to write {}", dfx_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_candid_path_keeps_formatting() {
+        let dfx_json = r#"{
+    "canisters": {
+        "other": {
+            "type": "assets"
+        },
+        "test": {
+            "package": "test",
+            "type": "rust"
+        }
+    },
+    "version": 1
+}
+"#;
+        let expected = r#"{
+    "canisters": {
+        "other": {
+            "type": "assets"
+        },
+        "test": {
+            "candid": "src/test/test.did",
+            "package": "test",
+            "type": "rust"
+        }
+    },
+    "version": 1
+}
+"#;
+        let result = insert_candid_path(dfx_json, "test", "src/test/test.did").unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_insert_candid_path_compact() {
+        let dfx_json = r#"{"canisters":{"test":{"package":"test","type":"rust"},"empty":{}}}"#;
+        let result = insert_candid_path(dfx_json, "test", "test.did").unwrap();
+        let result = insert_candid_path(&result, "empty", "empty.did").unwrap();
+        assert_eq!(
+            result,
+            r#"{"canisters":{"test":{"candid": "test.did", "package":"test","type":"rust"},"empty":{"candid": "empty.did"}}}"#
+        );
+    }
+
+    #[test]
+    fn test_insert_candid_path_ignores_nested_keys() {
+        let dfx_json = r#"{"defaults":{"test":{}},"canisters":{"other":{"test":{}}}}"#;
+        assert!(insert_candid_path(dfx_json, "test", "test.did").is_err());
+    }
+}
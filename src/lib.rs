@@ -2,4 +2,13 @@ pub mod args_options;
 pub mod functions;
 pub mod types;
 
-pub const BUILD_OUTPUT_DIR: &str = "target/wasm32-unknown-unknown/release";
+pub const BUILD_TARGET: &str = "wasm32-unknown-unknown";
+pub const BUILD_PROFILE: &str = "release";
+pub const DFX_ARTIFACTS_DIR: &str = ".dfx";
+pub const DFX_NETWORK: &str = "local";
+pub const CANDID_PATH_TEMPLATE: &str = "src/{name}/{name}.did";
+pub const SETTINGS_FILE: &str = "candid-gen.toml";
+pub const SETTINGS_MANIFEST_TABLE: &str = "workspace.metadata.candid-gen";
+pub const ENV_VARS_PREFIX: &str = "CANDID_GEN_";
+pub const VCS_DIRS: &[&str] = &[".git", ".hg", ".jj"];
+pub const WATCH_DEBOUNCE_MS: u64 = 300;
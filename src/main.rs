@@ -1,39 +1,993 @@
-use std::{env::set_current_dir, fs::read_to_string};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env::{current_dir, set_current_dir},
+    fs::create_dir_all,
+    io::{stderr, stdout, IsTerminal},
+    path::{absolute, Path, PathBuf},
+    process::exit,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, RecvTimeoutError},
+        Mutex,
+    },
+    thread::{available_parallelism, scope},
+    time::Duration,
+};
 
-use anyhow::{anyhow, Context, Result};
-use candid_gen::args_options::Args;
-use candid_gen::functions::build_wasm32::build_wasm32;
+use anyhow::Result;
+use candid_gen::args_options::{Args, Command, ConfigCommand};
+use candid_gen::functions::build_wasm32_packages::build_wasm32_packages;
+use candid_gen::functions::check_candid::check_candid;
+use candid_gen::functions::diff_against::diff_against;
+use candid_gen::functions::find_dfx_jsons::find_dfx_jsons;
 use candid_gen::functions::gen_candid::gen_candid;
+use candid_gen::functions::gen_candid_from_metadata::gen_candid_from_metadata;
+use candid_gen::functions::gen_declarations::gen_declarations;
+use candid_gen::functions::gen_motoko_bindings::gen_motoko_bindings;
+use candid_gen::functions::gen_rust_bindings::gen_rust_bindings;
+use candid_gen::functions::get_candid_cache_path::get_candid_cache_path;
+use candid_gen::functions::get_cargo_metadata::get_cargo_metadata;
+use candid_gen::functions::get_dfx_artifact_path::get_dfx_artifact_path;
+use candid_gen::functions::get_manifest_path::get_manifest_path;
 use candid_gen::functions::get_project_root::get_project_root;
+use candid_gen::functions::get_sources_modified::get_sources_modified;
+use candid_gen::functions::get_wasm_path_str::get_wasm_path_str;
+use candid_gen::functions::get_watch_paths::{get_watch_paths, is_source_file};
+use candid_gen::functions::post_process_candid::post_process_candid;
+use candid_gen::functions::print_message::{capture_messages, print_message};
+use candid_gen::functions::read_settings_file::read_settings_file;
+use candid_gen::functions::read_wasm::read_wasm;
+use candid_gen::functions::read_wasm_candid::read_wasm_candid;
 use candid_gen::functions::run_command::run_command;
-use candid_gen::types::canisters::Canisters;
-use candid_gen::types::dfx_cfg::DfxCfg;
+use candid_gen::functions::update_candid::update_candid;
+use candid_gen::functions::write_dfx_candid_paths::write_dfx_candid_paths;
+use candid_gen::types::build_options::BuildOptions;
+use candid_gen::types::candid_cache::{post_process_hash, sha256_hex, CandidCache, CandidSource};
+use candid_gen::types::canisters::{unmatched_selectors_error, Canisters};
+use candid_gen::types::dfx_cfg::{DfxCfg, RustCanisterCfg};
+use candid_gen::types::error::CandidGenError;
+use candid_gen::types::progress::Progress;
+use candid_gen::types::settings::{SettingSource, Settings, SettingsLayer};
+use candid_gen::WATCH_DEBOUNCE_MS;
+use cargo_metadata::Metadata;
 use clap::Parser;
+use notify::{recommended_watcher, EventKind, RecursiveMode, Watcher};
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    run_command("rustup --version")?;
-    run_command("cargo --version")?;
-    run_command("candid-extractor --version")?;
-    let installed_targets = run_command("rustup target list --installed")?;
+fn check_toolchain() -> Result<(), CandidGenError> {
+    for tool in ["rustup", "cargo"] {
+        run_command(&format!("{} --version", tool)).map_err(|e| {
+            CandidGenError::MissingTool(format!("'{}' is not available: {}", tool, e))
+        })?;
+    }
+    let installed_targets = run_command("rustup target list --installed")
+        .map_err(|e| CandidGenError::MissingTool(e.to_string()))?;
     if !installed_targets.contains("wasm32-unknown-unknown") {
-        return Err(anyhow!(
-            "rustup doesn't have the target wasm32-unknown-unknown installed"
+        return Err(CandidGenError::MissingTool(
+            "rustup doesn't have the target wasm32-unknown-unknown installed".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the wasm hash of the canister when its `.did` file was regenerated,
+/// to post-process and cache it once every canister is processed.
+fn process_canister(
+    args: &Args,
+    project_root: &Path,
+    settings: &Settings,
+    canister_name: &str,
+    canister: &RustCanisterCfg,
+    wasm_path_str: &str,
+    cache: &Mutex<CandidCache>,
+) -> Result<Option<String>> {
+    if let Some(Command::Diff { against, .. }) = &args.command {
+        if !diff_against(
+            project_root,
+            canister,
+            wasm_path_str,
+            args.from_metadata,
+            against,
+        )? && settings.deny_breaking().value
+        {
+            return Err(CandidGenError::Breaking {
+                canister: canister_name.to_string(),
+            }
+            .into());
+        }
+        return Ok(None);
+    }
+    let candid_path = project_root.join(&canister.candid_file_path_str);
+    let wasm_hash = sha256_hex(&read_wasm(wasm_path_str)?);
+    let post_process_hash = post_process_hash(&canister.candid_gen_cfg()?.post_process);
+    let source = CandidSource {
+        wasm_hash: &wasm_hash,
+        extraction_mode: args.extraction_mode(),
+        post_process_hash: &post_process_hash,
+    };
+    let is_fresh = !args.force
+        && cache
+            .lock()
+            .unwrap()
+            .is_fresh(canister_name, &source, &candid_path);
+    if is_fresh {
+        print_message(format!(
+            "candid-gen: Canister '{}' is unchanged since the last run, skipping the extraction.",
+            canister_name
+        ));
+    }
+    if args.check {
+        if !is_fresh && !check_candid(project_root, canister, wasm_path_str, args.from_metadata)? {
+            return Err(CandidGenError::Outdated {
+                canister: canister_name.to_string(),
+                path: canister.candid_file_path_str.clone(),
+            }
+            .into());
+        }
+        cache
+            .lock()
+            .unwrap()
+            .update(canister_name, &source, &candid_path);
+        return Ok(None);
+    }
+    if !is_fresh {
+        if args.from_metadata {
+            gen_candid_from_metadata(
+                project_root,
+                canister,
+                wasm_path_str,
+                settings.deny_breaking().value,
+            )?;
+        } else {
+            gen_candid(
+                project_root,
+                canister,
+                wasm_path_str,
+                settings.deny_breaking().value,
+            )?;
+        }
+    }
+    Ok((!is_fresh).then_some(wasm_hash))
+}
+
+/// Runs the post-processing commands of a regenerated canister and caches its `.did` file.
+fn post_process_canister(
+    project_root: &Path,
+    canister_name: &str,
+    canister: &RustCanisterCfg,
+    wasm_hash: &str,
+    extraction_mode: &str,
+    cache: &mut CandidCache,
+) -> Result<()> {
+    let post_process = canister.candid_gen_cfg()?.post_process;
+    post_process_candid(project_root, canister_name, canister, &post_process)?;
+    cache.update(
+        canister_name,
+        &CandidSource {
+            wasm_hash,
+            extraction_mode,
+            post_process_hash: &post_process_hash(&post_process),
+        },
+        &project_root.join(&canister.candid_file_path_str),
+    );
+    Ok(())
+}
+
+/// Builds the canisters with one `cargo build` per distinct set of build options
+/// and returns the path of their wasm files.
+fn build_canisters<'a>(
+    canisters: &[(&'a str, &'a RustCanisterCfg, BuildOptions)],
+) -> BTreeMap<&'a str, Result<String, CandidGenError>> {
+    let mut build_groups: Vec<(&BuildOptions, Vec<(&'a str, &'a RustCanisterCfg)>)> = Vec::new();
+    for (canister_name, canister, build_options) in canisters {
+        match build_groups
+            .iter_mut()
+            .find(|(group_build_options, _)| *group_build_options == build_options)
+        {
+            Some((_, group_canisters)) => group_canisters.push((canister_name, canister)),
+            None => build_groups.push((build_options, vec![(canister_name, canister)])),
+        }
+    }
+
+    let mut build_results = BTreeMap::new();
+    for (build_options, group_canisters) in build_groups {
+        let packages: Vec<&RustCanisterCfg> = group_canisters
+            .iter()
+            .map(|(_, canister)| *canister)
+            .collect();
+        let package_results = build_wasm32_packages(&packages, build_options);
+        for (canister_name, canister) in group_canisters {
+            build_results.insert(canister_name, package_results[&canister.package].clone());
+        }
+    }
+    build_results
+}
+
+/// Processes the built canisters on `args.jobs` threads.
+/// The canisters missing from `build_results` are read from their existing wasm file.
+fn process_canisters<'a>(
+    args: &Args,
+    project_root: &Path,
+    metadata: &Metadata,
+    settings: &Settings,
+    canisters: &[(&'a str, &'a RustCanisterCfg, BuildOptions)],
+    build_results: &BTreeMap<&str, Result<String, CandidGenError>>,
+    cache: &Mutex<CandidCache>,
+) -> BTreeMap<&'a str, Result<Option<String>>> {
+    let jobs = match args.jobs {
+        Some(jobs) => jobs as usize,
+        None => available_parallelism().map_or(1, |jobs| jobs.get()),
+    };
+    // The status line is drawn on stderr between the lines printed on stdout.
+    let progress = Progress::new(
+        canisters.len(),
+        stdout().is_terminal() && stderr().is_terminal(),
+    );
+    let next_canister = AtomicUsize::new(0);
+    let results = Mutex::new(BTreeMap::new());
+    scope(|s| {
+        for _ in 0..jobs.min(canisters.len()) {
+            s.spawn(|| {
+                while let Some((canister_name, canister, build_options)) =
+                    canisters.get(next_canister.fetch_add(1, Ordering::Relaxed))
+                {
+                    progress.start(canister_name);
+                    let (result, captured_messages) = capture_messages(|| {
+                        let wasm_path_str = match build_results.get(canister_name) {
+                            Some(build_result) => build_result.clone()?,
+                            None => get_wasm_path_str(canister, build_options, metadata)?,
+                        };
+                        process_canister(
+                            args,
+                            project_root,
+                            settings,
+                            canister_name,
+                            canister,
+                            &wasm_path_str,
+                            cache,
+                        )
+                    });
+                    progress.finish(canister_name, &captured_messages, result.as_ref().err());
+                    results.lock().unwrap().insert(*canister_name, result);
+                }
+            });
+        }
+    });
+    results.into_inner().unwrap()
+}
+
+fn warn_stale_dfx_artifacts(
+    metadata: &Metadata,
+    canisters: &[(&str, &RustCanisterCfg, BuildOptions)],
+) {
+    let target_dir = metadata.target_directory.as_std_path();
+    for (canister_name, canister, build_options) in canisters {
+        let Some(dfx_artifact) = &build_options.dfx_artifact else {
+            continue;
+        };
+        let Ok(artifact_modified) = dfx_artifact.metadata().and_then(|m| m.modified()) else {
+            continue;
+        };
+        let watch_paths = get_watch_paths(&canister.package, metadata);
+        if get_sources_modified(&watch_paths, target_dir)
+            .is_some_and(|sources_modified| sources_modified > artifact_modified)
+        {
+            eprintln!(
+                "candid-gen warning: The dfx artifact {} of the canister '{}' is older than its sources. Run 'dfx build' to update it.",
+                dfx_artifact.display(),
+                canister_name
+            );
+        }
+    }
+}
+
+/// Generates the declarations and the bindings of a canister from its post-processed `.did` file.
+fn gen_bindings(
+    project_root: &Path,
+    settings: &Settings,
+    canisters: &Canisters,
+    canister_name: &str,
+    canister: &RustCanisterCfg,
+) -> Result<()> {
+    let candid_gen_cfg = canister.candid_gen_cfg()?;
+    if settings.with_canister(&candid_gen_cfg).declarations().value {
+        gen_declarations(project_root, canister_name, canister)?;
+    }
+    if let Some(bindings_dir) = &candid_gen_cfg.motoko_bindings {
+        gen_motoko_bindings(
+            project_root,
+            canister_name,
+            canister,
+            Path::new(bindings_dir),
+        )?;
+    }
+    let Some(bindings_dir) = &candid_gen_cfg.rust_bindings else {
+        return Ok(());
+    };
+    for dependency_name in canister.dependencies() {
+        match canisters.0.get(dependency_name) {
+            Some(dependency) => gen_rust_bindings(
+                project_root,
+                dependency_name,
+                dependency,
+                Path::new(bindings_dir),
+            )?,
+            None => eprintln!(
+                "candid-gen: Skipping the Rust bindings of the dependency '{}' of the canister '{}': it is not a rust canister.",
+                dependency_name, canister_name
+            ),
+        }
+    }
+    Ok(())
+}
+
+fn print_canisters_results(results: &BTreeMap<String, Result<()>>, indent: &str) {
+    for (canister_name, result) in results {
+        match result {
+            Ok(()) => println!("{}ok      {}", indent, canister_name),
+            Err(e) => println!("{}failed  {}: {:#}", indent, canister_name, e),
+        }
+    }
+}
+
+fn print_summary(results: &BTreeMap<String, Result<()>>) {
+    println!("\ncandid-gen summary:");
+    print_canisters_results(results, "  ");
+}
+
+fn print_projects_summary(projects: &ProjectsResults) {
+    println!("\ncandid-gen summary:");
+    for (project, project_results) in projects {
+        println!("  {}", project);
+        match project_results {
+            Ok(results) => print_canisters_results(results, "    "),
+            Err(e) => println!("    failed  {}", e),
+        }
+    }
+}
+
+fn failed_canisters(results: &BTreeMap<String, Result<()>>) -> Vec<String> {
+    results
+        .iter()
+        .filter(|(_, result)| result.is_err())
+        .map(|(canister_name, _)| canister_name.to_string())
+        .collect()
+}
+
+type ProjectsResults = BTreeMap<String, Result<BTreeMap<String, Result<()>>, CandidGenError>>;
+
+fn extract(
+    wasm_path: &Path,
+    out_path: Option<&Path>,
+    from_metadata: bool,
+    deny_breaking: bool,
+) -> Result<(), CandidGenError> {
+    let wasm_path_str = wasm_path.display().to_string();
+    let candid =
+        read_wasm_candid(wasm_path, from_metadata).map_err(|e| CandidGenError::Extraction {
+            canister: wasm_path_str.clone(),
+            message: format!("{:#}", e),
+        })?;
+    let Some(out_path) = out_path else {
+        println!("{}", candid.trim_end());
+        return Ok(());
+    };
+    let out_path_str = out_path.to_string_lossy().to_string();
+    let to_write_error = |message: String| CandidGenError::Write {
+        path: out_path_str.clone(),
+        message,
+    };
+    if let Some(out_dir) = out_path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        create_dir_all(out_dir).map_err(|e| to_write_error(e.to_string()))?;
+    }
+    update_candid(&wasm_path_str, &out_path_str, &candid, deny_breaking).map_err(|e| {
+        e.downcast::<CandidGenError>()
+            .unwrap_or_else(|e| to_write_error(format!("{:#}", e)))
+    })
+}
+
+fn explain(settings: &Settings, canisters: &Canisters) -> Result<(), CandidGenError> {
+    let canisters: BTreeMap<&String, &RustCanisterCfg> = canisters.0.iter().collect();
+    for (canister_name, canister) in canisters {
+        let candid_gen_cfg = canister.candid_gen_cfg().map_err(|e| {
+            CandidGenError::Config(format!("Canister '{}': {:#}", canister_name, e))
+        })?;
+        let mut rows = settings.with_canister(&candid_gen_cfg).explain();
+        let candid_path_template = settings.candid_path_template();
+        let candid_source = match candid_gen_cfg.output.is_none()
+            && canister.candid_file_path_str
+                == RustCanisterCfg::candid_path_from_template(
+                    &candid_path_template.value,
+                    canister_name,
+                    &canister.package,
+                ) {
+            true => candid_path_template.source.describe("candid_path_template"),
+            false => SettingSource::DfxJson.to_string(),
+        };
+        rows.insert(
+            0,
+            (
+                "candid".to_string(),
+                canister.candid_file_path_str.clone(),
+                candid_source,
+            ),
+        );
+        for (key, value) in [
+            ("rust_bindings", &candid_gen_cfg.rust_bindings),
+            ("motoko_bindings", &candid_gen_cfg.motoko_bindings),
+        ] {
+            if let Some(value) = value {
+                rows.push((
+                    key.to_string(),
+                    value.clone(),
+                    SettingSource::DfxJson.to_string(),
+                ));
+            }
+        }
+        for command in candid_gen_cfg.post_process.iter() {
+            rows.push((
+                "post_process".to_string(),
+                command.clone(),
+                SettingSource::DfxJson.to_string(),
+            ));
+        }
+        let key_width = rows.iter().map(|(key, _, _)| key.len()).max().unwrap_or(0);
+        let value_width = rows
+            .iter()
+            .map(|(_, value, _)| value.len())
+            .max()
+            .unwrap_or(0);
+        println!("candid-gen: Canister '{}'", canister_name);
+        for (key, value, source) in rows {
+            println!(
+                "  {:key_width$}  {:value_width$}  ({})",
+                key,
+                value,
+                source,
+                key_width = key_width,
+                value_width = value_width
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the `dfx.json` and the root directory of the project.
+fn locate_project(args: &Args) -> Result<(PathBuf, PathBuf), CandidGenError> {
+    let to_config_error = |e: std::io::Error| CandidGenError::Config(e.to_string());
+    let dfx_path = match (&args.dfx_json, &args.project_root) {
+        (Some(dfx_json), _) => absolute(dfx_json).map_err(to_config_error)?,
+        (None, Some(project_root)) => {
+            absolute(project_root.join("dfx.json")).map_err(to_config_error)?
+        }
+        (None, None) => get_project_root()
+            .map_err(|e| CandidGenError::Config(e.to_string()))?
+            .join("dfx.json"),
+    };
+    let project_root = match (&args.project_root, dfx_path.parent()) {
+        (Some(project_root), _) => absolute(project_root).map_err(to_config_error)?,
+        (None, Some(dfx_dir)) => dfx_dir.to_path_buf(),
+        (None, None) => {
+            return Err(CandidGenError::Config(format!(
+                "Invalid dfx.json path {}",
+                dfx_path.display()
+            )))
+        }
+    };
+    Ok((dfx_path, project_root))
+}
+
+/// Stacks the settings of the command line, the environment and the settings file.
+fn load_settings(
+    args: &Args,
+    project_root: &Path,
+    manifest_path: &Path,
+) -> Result<Settings, CandidGenError> {
+    let mut settings = Settings(vec![
+        (SettingSource::CommandLine, args.settings_layer()),
+        (
+            SettingSource::Env,
+            SettingsLayer::from_env_vars(std::env::vars())?,
+        ),
+    ]);
+    if let Some(settings_file_layer) = read_settings_file(project_root, manifest_path)
+        .map_err(|e| CandidGenError::Config(format!("{:#}", e)))?
+    {
+        settings.0.push(settings_file_layer);
+    }
+    Ok(settings)
+}
+
+/// Returns the Cargo manifest and metadata, the settings and the rust canisters of the project.
+fn load_project(
+    args: &Args,
+    dfx_path: &Path,
+    project_root: &Path,
+) -> Result<(PathBuf, Metadata, Settings, Canisters), CandidGenError> {
+    let manifest_path = match &args.manifest_path {
+        Some(manifest_path) => {
+            absolute(manifest_path).map_err(|e| CandidGenError::Config(e.to_string()))?
+        }
+        None => get_manifest_path(project_root)
+            .map_err(|e| CandidGenError::Config(format!("{:#}", e)))?,
+    };
+    if !manifest_path.is_file() {
+        return Err(CandidGenError::Config(format!(
+            "{} does not exist",
+            manifest_path.display()
+        )));
+    }
+    let metadata = get_cargo_metadata(Some(&manifest_path))
+        .map_err(|e| CandidGenError::Config(format!("{:#}", e)))?;
+    let settings = load_settings(args, project_root, &manifest_path)?;
+    let mut dfx_cfg = DfxCfg::from_file(dfx_path)?;
+    for (canister_name, reason) in dfx_cfg.skipped_canisters.iter() {
+        eprintln!(
+            "candid-gen warning: {}: skipping the rust canister '{}': {}",
+            dfx_path.display(),
+            canister_name,
+            reason
+        );
+    }
+    if settings.strict().value && !dfx_cfg.skipped_canisters.is_empty() {
+        return Err(CandidGenError::Config(format!(
+            "{}: invalid rust canisters: {}",
+            dfx_path.display(),
+            dfx_cfg
+                .skipped_canisters
+                .iter()
+                .map(|(canister_name, _)| canister_name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        )));
+    }
+    let derived_candid_paths = dfx_cfg
+        .canisters
+        .fill_candid_paths(&settings.candid_path_template().value);
+    for (canister_name, candid_path) in derived_candid_paths.iter() {
+        println!(
+            "candid-gen: Canister '{}' has no candid entry in dfx.json, using {}.",
+            canister_name, candid_path
+        );
+    }
+    if args.write_dfx_json && !derived_candid_paths.is_empty() {
+        write_dfx_candid_paths(dfx_path, &derived_candid_paths)
+            .map_err(|e| CandidGenError::Config(format!("{:#}", e)))?;
+    }
+    Ok((manifest_path, metadata, settings, dfx_cfg.canisters))
+}
+
+/// `--check` and `diff` compare the extracted interface with the `.did` file, which the
+/// post-processing commands may have changed, so they reject the canisters that have some.
+fn reject_post_process(args: &Args, canisters: &Canisters) -> Result<(), CandidGenError> {
+    let mode = match args.command {
+        Some(Command::Diff { .. }) => "diff",
+        _ if args.check => "--check",
+        _ => return Ok(()),
+    };
+    let mut post_processed_canisters: Vec<&str> = canisters
+        .0
+        .iter()
+        .filter(|(_, canister)| {
+            canister
+                .candid_gen_cfg()
+                .is_ok_and(|candid_gen_cfg| !candid_gen_cfg.post_process.is_empty())
+        })
+        .map(|(canister_name, _)| canister_name.as_str())
+        .collect();
+    if post_processed_canisters.is_empty() {
+        return Ok(());
+    }
+    post_processed_canisters.sort();
+    Err(CandidGenError::Config(format!(
+        "{} cannot be used with the 'post_process' commands of: {}",
+        mode,
+        post_processed_canisters.join(", ")
+    )))
+}
+
+fn enter_project(project_root: &Path) -> Result<(), CandidGenError> {
+    set_current_dir(project_root)
+        .map_err(|e| CandidGenError::Config(format!("{}: {}", project_root.display(), e)))
+}
+
+fn run(args: &Args) -> Result<(), CandidGenError> {
+    if let Some(Command::Extract { wasm, out }) = &args.command {
+        return extract(wasm, out.as_deref(), args.from_metadata, args.deny_breaking);
+    }
+    check_toolchain()?;
+    if args.recursive {
+        return run_recursive(args);
+    }
+    let (dfx_path, project_root) = locate_project(args)?;
+    let (manifest_path, metadata, settings, canisters) =
+        load_project(args, &dfx_path, &project_root)?;
+    let canisters_to_gen_candid: Canisters = canisters.filter(&args.canister_selection())?;
+    if let Some(Command::Config {
+        command: ConfigCommand::Explain { .. },
+    }) = args.command
+    {
+        return explain(&settings, &canisters_to_gen_candid);
+    }
+    reject_post_process(args, &canisters_to_gen_candid)?;
+    enter_project(&project_root)?;
+    if let Some(Command::Watch { .. }) = args.command {
+        return watch(
+            args,
+            &project_root,
+            &manifest_path,
+            &metadata,
+            &settings,
+            &canisters,
+            &canisters_to_gen_candid,
+        );
+    }
+    let results = gen_canisters(
+        args,
+        &project_root,
+        &manifest_path,
+        &metadata,
+        &settings,
+        &canisters,
+        &canisters_to_gen_candid,
+    );
+    print_summary(&results);
+    let failed_canisters = failed_canisters(&results);
+    if !failed_canisters.is_empty() {
+        return Err(CandidGenError::Failed {
+            canisters: failed_canisters,
+        });
+    }
+    Ok(())
+}
+
+fn run_recursive(args: &Args) -> Result<(), CandidGenError> {
+    match args.command {
+        Some(Command::Watch { .. }) => {
+            return Err(CandidGenError::Config(
+                "--recursive cannot be used with watch".to_string(),
+            ))
+        }
+        Some(Command::Config { .. }) => {
+            return Err(CandidGenError::Config(
+                "--recursive cannot be used with config".to_string(),
+            ))
+        }
+        _ => (),
+    }
+    if args.manifest_path.is_some() {
+        return Err(CandidGenError::Config(
+            "--recursive cannot be used with --manifest-path".to_string(),
+        ));
+    }
+    if args.dfx_json.is_some() {
+        return Err(CandidGenError::Config(
+            "--recursive cannot be used with --dfx-json".to_string(),
         ));
     }
-    let project_root = get_project_root()?;
-    set_current_dir(&project_root)?;
-    let dfx_path = project_root.join("dfx.json");
-    let dfx_json = read_to_string(dfx_path).context("Failed to read dfx.json file")?;
-    let dfx_cfg: DfxCfg = serde_json::from_str(&dfx_json).unwrap();
-    let canisters: Canisters = dfx_cfg.canisters;
-    let canisters_to_gen_candid: Canisters = canisters.filter(&args.canisters_names);
-    for (_, canister) in canisters_to_gen_candid.0.iter() {
-        if let Err(e) = build_wasm32(canister) {
-            eprint!("{}", e);
+    let to_config_error = |e: std::io::Error| CandidGenError::Config(e.to_string());
+    let search_root = match &args.project_root {
+        Some(project_root) => absolute(project_root).map_err(to_config_error)?,
+        None => current_dir().map_err(to_config_error)?,
+    };
+    let dfx_paths = find_dfx_jsons(&search_root);
+    if dfx_paths.is_empty() {
+        return Err(CandidGenError::Config(format!(
+            "No dfx.json found below {}",
+            search_root.display()
+        )));
+    }
+
+    let mut projects = Vec::new();
+    let mut projects_results: ProjectsResults = BTreeMap::new();
+    for dfx_path in dfx_paths {
+        let project_root = dfx_path.parent().unwrap_or(&search_root).to_path_buf();
+        let project_name = match project_root.strip_prefix(&search_root) {
+            Ok(relative_root) if relative_root.as_os_str().is_empty() => ".".to_string(),
+            Ok(relative_root) => relative_root.display().to_string(),
+            Err(_) => project_root.display().to_string(),
+        };
+        match load_project(args, &dfx_path, &project_root) {
+            Ok((manifest_path, metadata, settings, canisters)) => projects.push((
+                project_name,
+                project_root,
+                manifest_path,
+                metadata,
+                settings,
+                canisters,
+            )),
+            Err(e) => {
+                eprintln!("candid-gen error: {}: {}", project_name, e);
+                projects_results.insert(project_name, Err(e));
+            }
+        }
+    }
+    let canister_selection = args.canister_selection();
+    let mut projects_canisters_to_gen_candid = Vec::new();
+    let mut unmatched_selectors: Option<Vec<String>> = None;
+    for (_, _, _, _, _, canisters) in projects.iter() {
+        let (canisters_to_gen_candid, project_unmatched_selectors) =
+            canisters.select(&canister_selection)?;
+        unmatched_selectors = Some(match unmatched_selectors {
+            Some(unmatched_selectors) => unmatched_selectors
+                .into_iter()
+                .filter(|selector| project_unmatched_selectors.contains(selector))
+                .collect(),
+            None => project_unmatched_selectors,
+        });
+        projects_canisters_to_gen_candid.push(canisters_to_gen_candid);
+    }
+    if let Some(unmatched_selectors) = unmatched_selectors.filter(|s| !s.is_empty()) {
+        return Err(unmatched_selectors_error(&unmatched_selectors));
+    }
+    for canisters_to_gen_candid in projects_canisters_to_gen_candid.iter() {
+        reject_post_process(args, canisters_to_gen_candid)?;
+    }
+    if !canister_selection.is_all()
+        && !projects.is_empty()
+        && projects_canisters_to_gen_candid
+            .iter()
+            .all(|canisters_to_gen_candid| canisters_to_gen_candid.0.is_empty())
+    {
+        return Err(CandidGenError::Config(
+            "The selection matches no canister.".to_string(),
+        ));
+    }
+
+    for (
+        (project_name, project_root, manifest_path, metadata, settings, canisters),
+        canisters_to_gen_candid,
+    ) in projects.iter().zip(projects_canisters_to_gen_candid)
+    {
+        if canisters_to_gen_candid.0.is_empty() {
+            continue;
+        }
+        println!("\ncandid-gen: Project {}", project_name);
+        let project_results = enter_project(project_root).map(|()| {
+            gen_canisters(
+                args,
+                project_root,
+                manifest_path,
+                metadata,
+                settings,
+                canisters,
+                &canisters_to_gen_candid,
+            )
+        });
+        projects_results.insert(project_name.clone(), project_results);
+    }
+
+    print_projects_summary(&projects_results);
+    let failed_canisters: Vec<String> = projects_results
+        .iter()
+        .flat_map(|(project_name, project_results)| match project_results {
+            Ok(results) => failed_canisters(results)
+                .into_iter()
+                .map(|canister_name| format!("{}/{}", project_name, canister_name))
+                .collect(),
+            Err(_) => vec![project_name.clone()],
+        })
+        .collect();
+    if !failed_canisters.is_empty() {
+        return Err(CandidGenError::Failed {
+            canisters: failed_canisters,
+        });
+    }
+    Ok(())
+}
+
+fn gen_canisters(
+    args: &Args,
+    project_root: &Path,
+    manifest_path: &Path,
+    metadata: &Metadata,
+    settings: &Settings,
+    canisters: &Canisters,
+    canisters_to_gen_candid: &Canisters,
+) -> BTreeMap<String, Result<()>> {
+    let is_diff = matches!(args.command, Some(Command::Diff { .. }));
+    let mut results: BTreeMap<&str, Result<()>> = BTreeMap::new();
+    let mut canisters_to_process = Vec::new();
+    for (canister_name, canister) in canisters_to_gen_candid.0.iter() {
+        match canister.candid_gen_cfg() {
+            Ok(candid_gen_cfg) => {
+                let mut build_options = settings.with_canister(&candid_gen_cfg).build_options();
+                build_options.manifest_path = Some(manifest_path.to_path_buf());
+                if args.from_dfx_artifacts {
+                    build_options.dfx_artifact = Some(get_dfx_artifact_path(
+                        project_root,
+                        canister_name,
+                        &args.network,
+                    ));
+                }
+                canisters_to_process.push((canister_name.as_str(), canister, build_options))
+            }
+            Err(e) => {
+                eprintln!("candid-gen error: {:#}", e);
+                results.insert(canister_name, Err(e));
+            }
+        }
+    }
+    let build_results = match args.from_metadata || args.from_dfx_artifacts {
+        true => BTreeMap::new(),
+        false => build_canisters(&canisters_to_process),
+    };
+    if args.from_dfx_artifacts {
+        warn_stale_dfx_artifacts(metadata, &canisters_to_process);
+    }
+    let cache_path = get_candid_cache_path(metadata);
+    let cache = Mutex::new(CandidCache::load(&cache_path));
+    let processed_results = process_canisters(
+        args,
+        project_root,
+        metadata,
+        settings,
+        &canisters_to_process,
+        &build_results,
+        &cache,
+    );
+    let mut cache = cache.into_inner().unwrap();
+    // The post-processing commands may share state, like the git index, so they run one at a time.
+    for (canister_name, result) in processed_results {
+        let result = match result {
+            Ok(Some(wasm_hash)) => post_process_canister(
+                project_root,
+                canister_name,
+                &canisters_to_gen_candid.0[canister_name],
+                &wasm_hash,
+                args.extraction_mode(),
+                &mut cache,
+            )
+            .inspect_err(|e| eprintln!("candid-gen error: {:#}", e)),
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        };
+        results.insert(canister_name, result);
+    }
+    if !is_diff {
+        if let Err(e) = cache.save(&cache_path) {
+            eprintln!("candid-gen warning: {:#}", e);
+        }
+    }
+    if !is_diff && !args.check {
+        for (canister_name, canister) in canisters_to_gen_candid.0.iter() {
+            if !matches!(results.get(canister_name.as_str()), Some(Ok(()))) {
+                continue;
+            }
+            if let Err(e) = gen_bindings(project_root, settings, canisters, canister_name, canister)
+            {
+                eprintln!("candid-gen error: {:#}", e);
+                results.insert(canister_name, Err(e));
+            }
+        }
+    }
+    results
+        .into_iter()
+        .map(|(canister_name, result)| (canister_name.to_string(), result))
+        .collect()
+}
+
+fn watch(
+    args: &Args,
+    project_root: &Path,
+    manifest_path: &Path,
+    metadata: &Metadata,
+    settings: &Settings,
+    canisters: &Canisters,
+    canisters_to_gen_candid: &Canisters,
+) -> Result<(), CandidGenError> {
+    let target_dir = metadata.target_directory.as_std_path();
+    let canisters_watch_paths: Vec<(&String, &RustCanisterCfg, Vec<PathBuf>)> =
+        canisters_to_gen_candid
+            .0
+            .iter()
+            .map(|(canister_name, canister)| {
+                (
+                    canister_name,
+                    canister,
+                    get_watch_paths(&canister.package, metadata),
+                )
+            })
+            .collect();
+
+    let (sender, receiver) = channel();
+    let mut watcher =
+        recommended_watcher(sender).map_err(|e| CandidGenError::Config(e.to_string()))?;
+    let watch_paths: BTreeSet<&PathBuf> = canisters_watch_paths
+        .iter()
+        .flat_map(|(_, _, watch_paths)| watch_paths)
+        .collect();
+    for watch_path in watch_paths {
+        watcher
+            .watch(watch_path, RecursiveMode::Recursive)
+            .map_err(|e| {
+                CandidGenError::Config(format!("Failed to watch {}: {}", watch_path.display(), e))
+            })?;
+    }
+
+    print_summary(&gen_canisters(
+        args,
+        project_root,
+        manifest_path,
+        metadata,
+        settings,
+        canisters,
+        canisters_to_gen_candid,
+    ));
+    println!("candid-gen: Watching for changes...");
+    let debounce = Duration::from_millis(WATCH_DEBOUNCE_MS);
+    while let Ok(event) = receiver.recv() {
+        let mut changed_paths = BTreeSet::new();
+        let mut next_event = Ok(event);
+        loop {
+            match next_event {
+                Ok(Ok(event)) if !matches!(event.kind, EventKind::Access(_)) => changed_paths
+                    .extend(
+                        event
+                            .paths
+                            .into_iter()
+                            .filter(|path| is_source_file(path, target_dir)),
+                    ),
+                Ok(Ok(_)) => (),
+                Ok(Err(e)) => eprintln!("candid-gen warning: {}", e),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+            next_event = receiver.recv_timeout(debounce);
+        }
+        let mut affected_canisters = Canisters::new();
+        for (canister_name, canister, watch_paths) in canisters_watch_paths.iter() {
+            if changed_paths.iter().any(|changed_path| {
+                watch_paths
+                    .iter()
+                    .any(|watch_path| changed_path.starts_with(watch_path))
+            }) {
+                affected_canisters
+                    .0
+                    .insert(canister_name.to_string(), (*canister).clone());
+            }
+        }
+        if affected_canisters.0.is_empty() {
             continue;
         }
-        gen_candid(&project_root, canister)?;
+        // Refresh the Rust bindings that the dependents generate from the affected `.did` files.
+        for (canister_name, canister, _) in canisters_watch_paths.iter() {
+            if canister
+                .dependencies()
+                .iter()
+                .any(|dependency_name| affected_canisters.0.contains_key(*dependency_name))
+            {
+                affected_canisters
+                    .0
+                    .insert(canister_name.to_string(), (*canister).clone());
+            }
+        }
+        println!(
+            "candid-gen: Change detected, regenerating {}.",
+            affected_canisters
+                .0
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<&str>>()
+                .join(", ")
+        );
+        print_summary(&gen_canisters(
+            args,
+            project_root,
+            manifest_path,
+            metadata,
+            settings,
+            canisters,
+            &affected_canisters,
+        ));
+        println!("candid-gen: Watching for changes...");
     }
     Ok(())
 }
+
+fn main() {
+    let args = Args::parse();
+    if let Err(e) = run(&args) {
+        eprintln!("candid-gen error: {}", e);
+        exit(e.exit_code());
+    }
+}
@@ -0,0 +1,113 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{BUILD_PROFILE, BUILD_TARGET};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BuildOptions {
+    pub profile: Option<String>,
+    pub features: Vec<String>,
+    pub no_default_features: bool,
+    pub locked: bool,
+    pub offline: bool,
+    pub env: BTreeMap<String, String>,
+    /// Wasm file built by dfx, read instead of the cargo build output (`--from-dfx-artifacts`).
+    pub dfx_artifact: Option<PathBuf>,
+    /// `Cargo.toml` of the workspace of the canisters.
+    pub manifest_path: Option<PathBuf>,
+}
+
+impl BuildOptions {
+    pub fn profile(&self) -> &str {
+        self.profile.as_deref().unwrap_or(BUILD_PROFILE)
+    }
+
+    /// Directory of the profile under `target/<target>/`: cargo uses `debug` for `dev` and `test`,
+    /// `release` for `bench` and the profile name for every other profile.
+    pub fn profile_dir(&self) -> &str {
+        match self.profile() {
+            "dev" | "test" => "debug",
+            "bench" => "release",
+            profile => profile,
+        }
+    }
+
+    pub fn cargo_build_args(&self, packages: &[&str]) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            "build".to_string(),
+            "--profile".to_string(),
+            self.profile().to_string(),
+            "--target".to_string(),
+            BUILD_TARGET.to_string(),
+        ];
+        if let Some(manifest_path) = &self.manifest_path {
+            args.push("--manifest-path".to_string());
+            args.push(manifest_path.to_string_lossy().to_string());
+        }
+        for package in packages {
+            args.push("--package".to_string());
+            args.push(package.to_string());
+        }
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(","));
+        }
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        if self.locked {
+            args.push("--locked".to_string());
+        }
+        if self.offline {
+            args.push("--offline".to_string());
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_options_default() {
+        let build_options = BuildOptions::default();
+        assert_eq!(build_options.profile(), "release");
+        assert_eq!(build_options.profile_dir(), "release");
+        assert_eq!(
+            build_options.cargo_build_args(&["test_canister"]),
+            vec![
+                "build",
+                "--profile",
+                "release",
+                "--target",
+                "wasm32-unknown-unknown",
+                "--package",
+                "test_canister"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_options_cargo_build_args_packages() {
+        assert_eq!(
+            BuildOptions::default().cargo_build_args(&["a", "b"])[5..],
+            ["--package", "a", "--package", "b"]
+        );
+    }
+
+    #[test]
+    fn test_build_options_profile_dir() {
+        for (profile, profile_dir) in [
+            ("dev", "debug"),
+            ("test", "debug"),
+            ("bench", "release"),
+            ("canister", "canister"),
+        ] {
+            let build_options = BuildOptions {
+                profile: Some(profile.to_string()),
+                ..Default::default()
+            };
+            assert_eq!(build_options.profile_dir(), profile_dir);
+        }
+    }
+}
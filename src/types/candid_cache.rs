@@ -0,0 +1,197 @@
+use std::{
+    collections::BTreeMap,
+    fs::{create_dir_all, read, read_to_string, write},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const CANDID_GEN_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CandidCacheEntry {
+    pub candid_gen_version: String,
+    pub wasm_hash: String,
+    pub extraction_mode: String,
+    pub post_process_hash: String,
+    pub candid_path: String,
+    pub candid_hash: String,
+}
+
+/// Hashes of the wasm artifacts and of the `.did` files generated from them, with the
+/// extraction mode and `post_process` commands used, by canister name, stored under the
+/// cargo target directory.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct CandidCache {
+    pub canisters: BTreeMap<String, CandidCacheEntry>,
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Hashes the `post_process` commands run over a `.did` file.
+pub fn post_process_hash(post_process: &[String]) -> String {
+    sha256_hex(format!("{:?}", post_process).as_bytes())
+}
+
+/// What a `.did` file is generated from: a wasm, how its candid is read and how the
+/// file is post-processed.
+pub struct CandidSource<'a> {
+    pub wasm_hash: &'a str,
+    pub extraction_mode: &'a str,
+    pub post_process_hash: &'a str,
+}
+
+impl CandidCache {
+    /// Loads the cache, starting from an empty one when it is missing or unreadable.
+    pub fn load(cache_path: &Path) -> Self {
+        read_to_string(cache_path)
+            .ok()
+            .and_then(|cache_json| serde_json::from_str(&cache_json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, cache_path: &Path) -> Result<()> {
+        if let Some(cache_dir) = cache_path.parent() {
+            create_dir_all(cache_dir)
+                .with_context(|| format!("Failed to create {}", cache_dir.display()))?;
+        }
+        write(cache_path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", cache_path.display()))
+    }
+
+    /// Whether the `.did` file at `candid_path` was generated by this candid-gen version
+    /// from `source` and has not been modified since.
+    pub fn is_fresh(&self, canister_name: &str, source: &CandidSource, candid_path: &Path) -> bool {
+        let Some(entry) = self.canisters.get(canister_name) else {
+            return false;
+        };
+        entry.candid_gen_version == CANDID_GEN_VERSION
+            && entry.wasm_hash == source.wasm_hash
+            && entry.extraction_mode == source.extraction_mode
+            && entry.post_process_hash == source.post_process_hash
+            && Path::new(&entry.candid_path) == candid_path
+            && read(candid_path).is_ok_and(|candid| sha256_hex(&candid) == entry.candid_hash)
+    }
+
+    /// Records the `.did` file at `candid_path` as generated from `source`.
+    pub fn update(&mut self, canister_name: &str, source: &CandidSource, candid_path: &Path) {
+        match read(candid_path) {
+            Ok(candid) => {
+                self.canisters.insert(
+                    canister_name.to_string(),
+                    CandidCacheEntry {
+                        candid_gen_version: CANDID_GEN_VERSION.to_string(),
+                        wasm_hash: source.wasm_hash.to_string(),
+                        extraction_mode: source.extraction_mode.to_string(),
+                        post_process_hash: source.post_process_hash.to_string(),
+                        candid_path: candid_path.to_string_lossy().to_string(),
+                        candid_hash: sha256_hex(&candid),
+                    },
+                );
+            }
+            Err(_) => {
+                self.canisters.remove(canister_name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_process_hash() {
+        assert_ne!(
+            post_process_hash(&["a b".to_string()]),
+            post_process_hash(&["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    const SOURCE: CandidSource = CandidSource {
+        wasm_hash: "wasm_hash",
+        extraction_mode: "build",
+        post_process_hash: "post_process_hash",
+    };
+
+    #[test]
+    fn test_candid_cache_is_fresh() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let candid_path = temp_dir.path().join("test_canister.did");
+        write(&candid_path, "service : {}").expect("Failed to write the candid file");
+
+        let mut cache = CandidCache::default();
+        assert!(!cache.is_fresh("test_canister", &SOURCE, &candid_path));
+        cache.update("test_canister", &SOURCE, &candid_path);
+        assert!(cache.is_fresh("test_canister", &SOURCE, &candid_path));
+        assert!(!cache.is_fresh(
+            "test_canister",
+            &CandidSource {
+                wasm_hash: "other_wasm_hash",
+                ..SOURCE
+            },
+            &candid_path
+        ));
+        assert!(!cache.is_fresh(
+            "test_canister",
+            &CandidSource {
+                extraction_mode: "metadata",
+                ..SOURCE
+            },
+            &candid_path
+        ));
+        assert!(!cache.is_fresh(
+            "test_canister",
+            &CandidSource {
+                post_process_hash: "other_post_process_hash",
+                ..SOURCE
+            },
+            &candid_path
+        ));
+        assert!(!cache.is_fresh("other_canister", &SOURCE, &candid_path));
+
+        write(&candid_path, "service : { get : () -> (nat) }")
+            .expect("Failed to write the candid file");
+        assert!(!cache.is_fresh("test_canister", &SOURCE, &candid_path));
+
+        cache
+            .canisters
+            .get_mut("test_canister")
+            .unwrap()
+            .candid_gen_version = "0.0.0".to_string();
+        cache.update("test_canister", &SOURCE, &candid_path);
+        assert!(cache.is_fresh("test_canister", &SOURCE, &candid_path));
+    }
+
+    #[test]
+    fn test_candid_cache_save_and_load() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let cache_path = temp_dir.path().join("candid-gen/cache.json");
+        assert_eq!(CandidCache::load(&cache_path), CandidCache::default());
+
+        let candid_path = temp_dir.path().join("test_canister.did");
+        write(&candid_path, "service : {}").expect("Failed to write the candid file");
+        let mut cache = CandidCache::default();
+        cache.update("test_canister", &SOURCE, &candid_path);
+        cache.save(&cache_path).expect("Failed to save the cache");
+        assert_eq!(CandidCache::load(&cache_path), cache);
+
+        write(&cache_path, "not json").expect("Failed to write the cache");
+        assert_eq!(CandidCache::load(&cache_path), CandidCache::default());
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// The `candid_gen` settings of a rust canister in `dfx.json`.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CandidGenCfg {
+    /// `.did` file written by candid-gen, instead of the `candid` entry of the canister.
+    pub output: Option<String>,
+    /// Leave the canister out of candid-gen, unless it is a dependency of another one.
+    #[serde(default)]
+    pub skip: bool,
+    /// Tags that select the canister with `--tag`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Shell commands run from the project root after the `.did` file is generated.
+    /// `{path}` is replaced by the path of the `.did` file and `{name}` by the canister name.
+    #[serde(default)]
+    pub post_process: Vec<String>,
+    pub declarations: Option<bool>,
+    pub rust_bindings: Option<String>,
+    pub motoko_bindings: Option<String>,
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    pub no_default_features: Option<bool>,
+    pub locked: Option<bool>,
+    pub offline: Option<bool>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+impl CandidGenCfg {
+    /// Checks the values that deserialize fine but cannot be used.
+    pub fn validate(&self) -> Result<(), String> {
+        for (key, value) in [
+            ("output", &self.output),
+            ("rust_bindings", &self.rust_bindings),
+            ("motoko_bindings", &self.motoko_bindings),
+            ("profile", &self.profile),
+        ] {
+            if value
+                .as_deref()
+                .is_some_and(|value| value.trim().is_empty())
+            {
+                return Err(format!("'{}' must not be empty", key));
+            }
+        }
+        if let Some(output) = &self.output {
+            if !output.ends_with(".did") {
+                return Err(format!("'output' must be a .did file, got '{}'", output));
+            }
+        }
+        if let Some(tag) = self
+            .tags
+            .iter()
+            .find(|tag| tag.trim().is_empty() || tag.contains(','))
+        {
+            return Err(format!("invalid tag '{}'", tag));
+        }
+        if self
+            .post_process
+            .iter()
+            .any(|command| command.trim().is_empty())
+        {
+            return Err("'post_process' commands must not be empty".to_string());
+        }
+        if let Some(feature) = self
+            .features
+            .iter()
+            .find(|feature| feature.is_empty() || feature.contains([',', ' ']))
+        {
+            return Err(format!(
+                "invalid feature '{}': list each feature as a separate string",
+                feature
+            ));
+        }
+        if let Some(key) = self
+            .env
+            .keys()
+            .find(|key| key.is_empty() || key.contains('='))
+        {
+            return Err(format!("invalid 'env' variable name '{}'", key));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_candid_gen_cfg_unknown_field() {
+        let error = serde_json::from_value::<CandidGenCfg>(json!({ "outptu": "a.did" }))
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("unknown field `outptu`, expected one of `output`"));
+    }
+
+    #[test]
+    fn test_candid_gen_cfg_validate() {
+        let candid_gen_cfg: CandidGenCfg = serde_json::from_value(json!({
+            "output": "candid/wallet.did",
+            "skip": false,
+            "tags": ["ledger", "core"],
+            "post_process": ["git add {path}"],
+            "declarations": true,
+            "features": ["export-candid"]
+        }))
+        .unwrap();
+        assert_eq!(candid_gen_cfg.validate(), Ok(()));
+
+        for (candid_gen_cfg, error) in [
+            (json!({ "output": "" }), "'output' must not be empty"),
+            (
+                json!({ "output": "wallet.json" }),
+                "'output' must be a .did file, got 'wallet.json'",
+            ),
+            (
+                json!({ "post_process": [" "] }),
+                "'post_process' commands must not be empty",
+            ),
+            (
+                json!({ "features": ["a,b"] }),
+                "invalid feature 'a,b': list each feature as a separate string",
+            ),
+        ] {
+            let candid_gen_cfg: CandidGenCfg = serde_json::from_value(candid_gen_cfg).unwrap();
+            assert_eq!(candid_gen_cfg.validate(), Err(error.to_string()));
+        }
+    }
+}
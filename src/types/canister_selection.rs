@@ -0,0 +1,71 @@
+use glob::Pattern;
+
+use super::error::CandidGenError;
+
+/// Canisters selected on the command line: names or glob patterns, `--exclude` patterns
+/// and `--tag`s. No patterns select every canister that is not skipped.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CanisterSelection {
+    pub patterns: Option<Vec<String>>,
+    pub exclude: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl CanisterSelection {
+    pub fn is_all(&self) -> bool {
+        self.patterns.is_none() && self.exclude.is_empty() && self.tags.is_empty()
+    }
+}
+
+/// A canister name or glob pattern (`*`, `?`, `[...]`).
+#[derive(Debug, Clone)]
+pub struct NamePattern {
+    pattern: String,
+    glob: Option<Pattern>,
+}
+
+impl NamePattern {
+    pub fn new(pattern: &str) -> Result<Self, CandidGenError> {
+        let glob = match pattern.contains(['*', '?', '[']) {
+            true => Some(Pattern::new(pattern).map_err(|e| {
+                CandidGenError::Config(format!("Invalid pattern '{}': {}", pattern, e))
+            })?),
+            false => None,
+        };
+        Ok(NamePattern {
+            pattern: pattern.to_string(),
+            glob,
+        })
+    }
+
+    pub fn is_glob(&self) -> bool {
+        self.glob.is_some()
+    }
+
+    pub fn matches(&self, canister_name: &str) -> bool {
+        match &self.glob {
+            Some(glob) => glob.matches(canister_name),
+            None => self.pattern == canister_name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_pattern_matches() {
+        let pattern = NamePattern::new("ledger_*").unwrap();
+        assert!(pattern.is_glob());
+        assert!(pattern.matches("ledger_index"));
+        assert!(!pattern.matches("wallet"));
+
+        let pattern = NamePattern::new("ledger").unwrap();
+        assert!(!pattern.is_glob());
+        assert!(pattern.matches("ledger"));
+        assert!(!pattern.matches("ledger_index"));
+
+        assert_eq!(NamePattern::new("ledger_[").unwrap_err().exit_code(), 2);
+    }
+}
@@ -1,6 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
-use super::dfx_cfg::RustCanisterCfg;
+use super::{
+    canister_selection::{CanisterSelection, NamePattern},
+    dfx_cfg::RustCanisterCfg,
+    error::CandidGenError,
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct Canisters(pub HashMap<String, RustCanisterCfg>);
@@ -9,27 +13,121 @@ impl Canisters {
     pub fn new() -> Self {
         Self(HashMap::new())
     }
-    pub fn filter(&self, names: &Option<Vec<String>>) -> Self {
-        match names {
-            Some(canisters_names) => canisters_names
-                .iter()
-                .fold(Canisters::new(), |mut map, name| {
-                    if let Some(canister) = self.0.get(name) {
-                        map.0.insert(name.clone(), canister.clone());
-                    } else {
-                        eprintln!(
-                            "candid-gen error: Not able to generate the candid file for the canister: {}.\n\
-                                Verify if it is a 'rust' canister type, or if the name is correct.\n",
-                            name
-                        );
+    /// Returns the selected canisters and the selectors that matched none of the canisters:
+    /// `'<pattern>'`, `--exclude '<pattern>'` or `--tag '<tag>'`.
+    /// Glob patterns ignore the skipped canisters, naming one of them is an error.
+    pub fn select(
+        &self,
+        selection: &CanisterSelection,
+    ) -> Result<(Self, Vec<String>), CandidGenError> {
+        let mut unmatched_selectors: Vec<String> = Vec::new();
+        let mut selected_names: BTreeSet<&String> = BTreeSet::new();
+        match &selection.patterns {
+            Some(patterns) => {
+                let mut skipped_names: Vec<&str> = Vec::new();
+                for pattern in patterns {
+                    let name_pattern = NamePattern::new(pattern)?;
+                    let mut is_matched = false;
+                    for (name, canister) in self.0.iter() {
+                        if !name_pattern.matches(name) {
+                            continue;
+                        }
+                        if !canister.is_skipped() {
+                            selected_names.insert(name);
+                            is_matched = true;
+                        } else if !name_pattern.is_glob() {
+                            skipped_names.push(name);
+                            is_matched = true;
+                        }
                     }
-                    map
-                }),
-            None => self.clone(),
+                    if !is_matched {
+                        unmatched_selectors.push(format!("'{}'", pattern));
+                    }
+                }
+                if !skipped_names.is_empty() {
+                    return Err(CandidGenError::Config(format!(
+                        "The canisters {} are skipped by their 'candid_gen' settings in dfx.json.",
+                        skipped_names.join(", ")
+                    )));
+                }
+            }
+            None => selected_names.extend(
+                self.0
+                    .iter()
+                    .filter(|(_, canister)| !canister.is_skipped())
+                    .map(|(name, _)| name),
+            ),
+        }
+        if !selection.tags.is_empty() {
+            for tag in selection.tags.iter() {
+                if !self
+                    .0
+                    .values()
+                    .any(|canister| canister.tags().contains(tag))
+                {
+                    unmatched_selectors.push(format!("--tag '{}'", tag));
+                }
+            }
+            selected_names.retain(|name| {
+                self.0[*name]
+                    .tags()
+                    .iter()
+                    .any(|tag| selection.tags.contains(tag))
+            });
+        }
+        for pattern in selection.exclude.iter() {
+            let name_pattern = NamePattern::new(pattern)?;
+            if !self.0.keys().any(|name| name_pattern.matches(name)) {
+                unmatched_selectors.push(format!("--exclude '{}'", pattern));
+            }
+            selected_names.retain(|name| !name_pattern.matches(name));
+        }
+        let canisters = Canisters(
+            selected_names
+                .into_iter()
+                .map(|name| (name.clone(), self.0[name].clone()))
+                .collect(),
+        );
+        Ok((canisters, unmatched_selectors))
+    }
+    /// Selects the canisters, failing when a selector matches no canister,
+    /// or when a restricted selection is left empty.
+    pub fn filter(&self, selection: &CanisterSelection) -> Result<Self, CandidGenError> {
+        let (canisters, unmatched_selectors) = self.select(selection)?;
+        if !unmatched_selectors.is_empty() {
+            return Err(unmatched_selectors_error(&unmatched_selectors));
         }
+        if canisters.0.is_empty() && !selection.is_all() {
+            return Err(CandidGenError::Config(
+                "The selection matches no canister.".to_string(),
+            ));
+        }
+        Ok(canisters)
+    }
+    pub fn fill_candid_paths(&mut self, template: &str) -> Vec<(String, String)> {
+        let mut filled_candid_paths: Vec<(String, String)> = self
+            .0
+            .iter_mut()
+            .filter(|(_, canister)| canister.candid_file_path_str.is_empty())
+            .map(|(name, canister)| {
+                canister.candid_file_path_str =
+                    RustCanisterCfg::candid_path_from_template(template, name, &canister.package);
+                (name.clone(), canister.candid_file_path_str.clone())
+            })
+            .collect();
+        filled_candid_paths.sort();
+        filled_candid_paths
     }
 }
 
+pub fn unmatched_selectors_error(unmatched_selectors: &[String]) -> CandidGenError {
+    CandidGenError::Config(format!(
+        "Not able to generate the candid file for: {}. \
+            Verify if they are 'rust' canisters, or if the names, patterns and tags are correct.",
+        unmatched_selectors.join(", ")
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,6 +139,42 @@ mod tests {
         assert!(canisters.0.is_empty());
     }
 
+    #[test]
+    fn test_canisters_fill_candid_paths() {
+        let mut canisters = Canisters::new();
+        canisters.0.insert(
+            "test1".to_string(),
+            RustCanisterCfg {
+                package: "test1".to_string(),
+                candid_file_path_str: "".to_string(),
+                other: HashMap::new(),
+            },
+        );
+        canisters.0.insert(
+            "test2".to_string(),
+            RustCanisterCfg {
+                package: "test2".to_string(),
+                candid_file_path_str: "src/test2/test2.did".to_string(),
+                other: HashMap::new(),
+            },
+        );
+
+        let filled_candid_paths = canisters.fill_candid_paths("candid/{name}-{package}.did");
+
+        assert_eq!(
+            filled_candid_paths,
+            vec![("test1".to_string(), "candid/test1-test1.did".to_string())]
+        );
+        assert_eq!(
+            canisters.0["test1"].candid_file_path_str,
+            "candid/test1-test1.did"
+        );
+        assert_eq!(
+            canisters.0["test2"].candid_file_path_str,
+            "src/test2/test2.did"
+        );
+    }
+
     #[test]
     fn test_canisters_filter_with_names() {
         let mut canisters = Canisters::new();
@@ -61,9 +195,12 @@ mod tests {
             },
         );
 
-        let names = Some(vec!["test1".to_string()]);
+        let selection = CanisterSelection {
+            patterns: Some(vec!["test1".to_string()]),
+            ..Default::default()
+        };
 
-        let filtered_canisters = canisters.filter(&names);
+        let filtered_canisters = canisters.filter(&selection).unwrap();
 
         assert_eq!(filtered_canisters.0.len(), 1);
         assert!(filtered_canisters.0.contains_key("test1"));
@@ -90,8 +227,7 @@ mod tests {
             },
         );
 
-        let names: Option<Vec<String>> = None;
-        let filtered_canisters = canisters.filter(&names);
+        let filtered_canisters = canisters.filter(&CanisterSelection::default()).unwrap();
 
         assert_eq!(filtered_canisters.0.len(), 2);
         assert_eq!(filtered_canisters.0, canisters.0);
@@ -117,10 +253,120 @@ mod tests {
             },
         );
 
-        let names = Some(vec!["test3".to_string()]);
+        let selection = CanisterSelection {
+            patterns: Some(vec!["test1".to_string(), "test3".to_string()]),
+            ..Default::default()
+        };
+
+        let filtered_canisters = canisters.filter(&selection);
+
+        assert!(filtered_canisters.is_err());
+        assert_eq!(filtered_canisters.unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn test_canisters_filter_skipped() {
+        let mut canisters = Canisters::new();
+        canisters
+            .0
+            .insert("test1".to_string(), RustCanisterCfg::new("test1"));
+        let mut skipped_canister = RustCanisterCfg::new("test2");
+        skipped_canister.other.insert(
+            "candid_gen".to_string(),
+            serde_json::json!({ "skip": true }),
+        );
+        canisters.0.insert("test2".to_string(), skipped_canister);
+
+        let filtered_canisters = canisters.filter(&CanisterSelection::default()).unwrap();
+        assert_eq!(
+            filtered_canisters.0.keys().collect::<Vec<&String>>(),
+            vec!["test1"]
+        );
+
+        let selection = CanisterSelection {
+            patterns: Some(vec!["test2".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(canisters.filter(&selection).unwrap_err().exit_code(), 2);
 
-        let filtered_canisters = canisters.filter(&names);
+        let selection = CanisterSelection {
+            patterns: Some(vec!["test*".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            canisters
+                .filter(&selection)
+                .unwrap()
+                .0
+                .keys()
+                .collect::<Vec<&String>>(),
+            vec!["test1"]
+        );
+    }
+
+    fn tagged_canister(name: &str, tags: &[&str]) -> RustCanisterCfg {
+        let mut canister = RustCanisterCfg::new(name);
+        canister.other.insert(
+            "candid_gen".to_string(),
+            serde_json::json!({ "tags": tags }),
+        );
+        canister
+    }
+
+    #[test]
+    fn test_canisters_select_patterns_exclude_and_tags() {
+        let mut canisters = Canisters::new();
+        for (name, tags) in [
+            ("ledger_archive", vec!["ledger"]),
+            ("ledger_index", vec!["ledger", "core"]),
+            ("ledger_main", vec!["ledger", "core"]),
+            ("wallet", vec!["core"]),
+        ] {
+            canisters
+                .0
+                .insert(name.to_string(), tagged_canister(name, &tags));
+        }
+        let selected_names = |selection: &CanisterSelection| {
+            let mut names: Vec<String> =
+                canisters.filter(selection).unwrap().0.into_keys().collect();
+            names.sort();
+            names
+        };
+
+        let selection = CanisterSelection {
+            patterns: Some(vec!["ledger_*".to_string()]),
+            exclude: vec!["*_archive".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            selected_names(&selection),
+            vec!["ledger_index", "ledger_main"]
+        );
+
+        let selection = CanisterSelection {
+            tags: vec!["core".to_string()],
+            exclude: vec!["ledger_main".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(selected_names(&selection), vec!["ledger_index", "wallet"]);
+
+        let selection = CanisterSelection {
+            patterns: Some(vec!["ledger_*".to_string(), "minter_*".to_string()]),
+            exclude: vec!["wallet_*".to_string()],
+            tags: vec!["ledger".to_string(), "frontend".to_string()],
+        };
+        let (selected_canisters, unmatched_selectors) = canisters.select(&selection).unwrap();
+        assert_eq!(selected_canisters.0.len(), 3);
+        assert_eq!(
+            unmatched_selectors,
+            vec!["'minter_*'", "--tag 'frontend'", "--exclude 'wallet_*'"]
+        );
+        assert_eq!(canisters.filter(&selection).unwrap_err().exit_code(), 2);
 
-        assert!(filtered_canisters.0.is_empty());
+        let selection = CanisterSelection {
+            exclude: vec!["*".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(canisters.filter(&selection).unwrap_err().exit_code(), 2);
     }
 }
@@ -1,18 +1,19 @@
 use core::fmt;
-use std::collections::HashMap;
+use std::{collections::HashMap, fs::read_to_string, path::Path};
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use serde::{
     de::{MapAccess, Visitor},
     Deserialize, Deserializer,
 };
 
-use super::canisters::Canisters;
+use super::{candid_gen_cfg::CandidGenCfg, canisters::Canisters, error::CandidGenError};
+use crate::CANDID_PATH_TEMPLATE;
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct RustCanisterCfg {
     pub package: String,
-    #[serde(rename = "candid")]
+    #[serde(rename = "candid", default)]
     pub candid_file_path_str: String,
     #[serde(flatten)]
     pub other: HashMap<String, serde_json::Value>,
@@ -22,15 +23,129 @@ impl RustCanisterCfg {
     pub fn new(canister_name: &str) -> Self {
         RustCanisterCfg {
             package: canister_name.to_owned(),
-            candid_file_path_str: format!("src/{}/{}.did", canister_name, canister_name),
+            candid_file_path_str: RustCanisterCfg::candid_path_from_template(
+                CANDID_PATH_TEMPLATE,
+                canister_name,
+                canister_name,
+            ),
             other: HashMap::default(),
         }
     }
+
+    pub fn candid_path_from_template(template: &str, canister_name: &str, package: &str) -> String {
+        template
+            .replace("{name}", canister_name)
+            .replace("{package}", package)
+    }
+
+    pub fn crate_name(&self) -> Option<&str> {
+        self.other.get("crate")?.as_str()
+    }
+
+    pub fn declarations_output(&self) -> Option<&str> {
+        self.other.get("declarations")?.get("output")?.as_str()
+    }
+
+    pub fn declarations_node_compatibility(&self) -> bool {
+        self.other
+            .get("declarations")
+            .and_then(|declarations| declarations.get("node_compatibility"))
+            .and_then(|node_compatibility| node_compatibility.as_bool())
+            .unwrap_or(false)
+    }
+
+    pub fn dependencies(&self) -> Vec<&str> {
+        self.other
+            .get("dependencies")
+            .and_then(|dependencies| dependencies.as_array())
+            .map(|dependencies| {
+                dependencies
+                    .iter()
+                    .filter_map(|dependency| dependency.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn candid_gen_cfg(&self) -> Result<CandidGenCfg> {
+        let candid_gen_cfg: CandidGenCfg = match self.other.get("candid_gen") {
+            Some(candid_gen_cfg) => serde_json::from_value(candid_gen_cfg.clone())
+                .context("Invalid 'candid_gen' settings")?,
+            None => CandidGenCfg::default(),
+        };
+        candid_gen_cfg
+            .validate()
+            .map_err(|e| anyhow!("Invalid 'candid_gen' settings: {}", e))?;
+        Ok(candid_gen_cfg)
+    }
+
+    pub fn tags(&self) -> Vec<String> {
+        self.candid_gen_cfg()
+            .map(|candid_gen_cfg| candid_gen_cfg.tags)
+            .unwrap_or_default()
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        self.candid_gen_cfg()
+            .is_ok_and(|candid_gen_cfg| candid_gen_cfg.skip)
+    }
 }
 
 #[derive(Debug)]
 pub struct DfxCfg {
     pub canisters: Canisters,
+    pub skipped_canisters: Vec<(String, String)>,
+}
+
+impl DfxCfg {
+    pub fn from_file(dfx_path: &Path) -> Result<Self, CandidGenError> {
+        let dfx_json = read_to_string(dfx_path).map_err(|e| {
+            CandidGenError::Config(format!("Failed to read {}: {}", dfx_path.display(), e))
+        })?;
+        let mut dfx_cfg: DfxCfg = serde_json::from_str(&dfx_json)
+            .map_err(|e| CandidGenError::Config(json_diagnostic(dfx_path, &dfx_json, &e)))?;
+        let mut invalid_canisters = Vec::new();
+        for (canister_name, canister) in dfx_cfg.canisters.0.iter_mut() {
+            match canister.candid_gen_cfg() {
+                Ok(candid_gen_cfg) => {
+                    if let Some(output) = candid_gen_cfg.output {
+                        canister.candid_file_path_str = output;
+                    }
+                }
+                Err(e) => invalid_canisters.push(format!("canister '{}': {:#}", canister_name, e)),
+            }
+        }
+        if !invalid_canisters.is_empty() {
+            invalid_canisters.sort();
+            return Err(CandidGenError::Config(format!(
+                "{}: {}",
+                dfx_path.display(),
+                invalid_canisters.join("; ")
+            )));
+        }
+        Ok(dfx_cfg)
+    }
+}
+
+fn json_diagnostic(path: &Path, json: &str, error: &serde_json::Error) -> String {
+    let (line, column) = (error.line(), error.column());
+    let error_str = error.to_string();
+    let message = error_str
+        .strip_suffix(&format!(" at line {} column {}", line, column))
+        .unwrap_or(&error_str);
+    let mut diagnostic = format!("{}:{}:{}: {}", path.display(), line, column, message);
+    if let Some(source_line) = json.lines().nth(line.saturating_sub(1)) {
+        let gutter = " ".repeat(line.to_string().len());
+        diagnostic.push_str(&format!(
+            "\n{} |\n{} | {}\n{} | {}^",
+            gutter,
+            line,
+            source_line,
+            gutter,
+            " ".repeat(column.saturating_sub(1))
+        ));
+    }
+    diagnostic
 }
 
 impl<'de> Deserialize<'de> for DfxCfg {
@@ -51,31 +166,33 @@ impl<'de> Deserialize<'de> for DfxCfg {
             where
                 V: MapAccess<'de>,
             {
-                // let mut canisters = HashMap::new();
                 let mut canisters = Canisters::new();
+                let mut skipped_canisters = Vec::new();
 
                 while let Some(key) = map.next_key::<String>()? {
                     if key == "canisters" {
                         let value: HashMap<String, serde_json::Value> = map.next_value()?;
                         for (canister_name, canister_value) in value {
-                            if let Ok(canister) =
-                                serde_json::from_value::<RustCanisterCfg>(canister_value.clone())
-                            {
-                                if let Some(canister_type) =
-                                    canister_value.get("type").and_then(|v| v.as_str())
-                                {
-                                    if canister_type == "rust" {
-                                        canisters.0.insert(canister_name.clone(), canister);
-                                    }
+                            if canister_value.get("type").and_then(|v| v.as_str()) != Some("rust") {
+                                continue;
+                            }
+                            match serde_json::from_value::<RustCanisterCfg>(canister_value) {
+                                Ok(canister) => {
+                                    canisters.0.insert(canister_name, canister);
                                 }
+                                Err(e) => skipped_canisters.push((canister_name, e.to_string())),
                             }
                         }
                     } else {
                         let _: serde_json::Value = map.next_value()?;
                     }
                 }
+                skipped_canisters.sort();
 
-                Ok(DfxCfg { canisters })
+                Ok(DfxCfg {
+                    canisters,
+                    skipped_canisters,
+                })
             }
         }
 
@@ -100,6 +217,61 @@ mod tests {
         assert!(canister.other.is_empty());
     }
 
+    #[test]
+    fn test_rust_canister_cfg_declarations() {
+        let canister: RustCanisterCfg = serde_json::from_value(json!({
+            "package": "test_canister",
+            "candid": "src/test_canister/test_canister.did",
+            "type": "rust",
+            "declarations": {
+                "output": "src/frontend/declarations/test_canister",
+                "node_compatibility": true
+            }
+        }))
+        .expect("Failed to deserialize");
+        assert_eq!(
+            canister.declarations_output(),
+            Some("src/frontend/declarations/test_canister")
+        );
+        assert!(canister.declarations_node_compatibility());
+
+        let canister = RustCanisterCfg::new("test_canister");
+        assert_eq!(canister.declarations_output(), None);
+        assert!(!canister.declarations_node_compatibility());
+    }
+
+    #[test]
+    fn test_rust_canister_cfg_dependencies_and_candid_gen_cfg() {
+        let canister: RustCanisterCfg = serde_json::from_value(json!({
+            "package": "test_canister",
+            "candid": "src/test_canister/test_canister.did",
+            "type": "rust",
+            "dependencies": ["ledger", "counter"],
+            "candid_gen": {
+                "rust_bindings": "src/test_canister/src/clients",
+                "profile": "canister",
+                "features": ["export-candid"],
+                "env": { "RUSTFLAGS": "-C opt-level=z" }
+            }
+        }))
+        .expect("Failed to deserialize");
+        assert_eq!(canister.dependencies(), vec!["ledger", "counter"]);
+        assert_eq!(canister.crate_name(), None);
+        assert_eq!(
+            canister.candid_gen_cfg().unwrap().rust_bindings.as_deref(),
+            Some("src/test_canister/src/clients")
+        );
+        let candid_gen_cfg = canister.candid_gen_cfg().unwrap();
+        assert_eq!(candid_gen_cfg.profile.as_deref(), Some("canister"));
+        assert_eq!(candid_gen_cfg.features, vec!["export-candid"]);
+        assert_eq!(candid_gen_cfg.locked, None);
+        assert_eq!(candid_gen_cfg.env["RUSTFLAGS"], "-C opt-level=z");
+
+        let canister = RustCanisterCfg::new("test_canister");
+        assert!(canister.dependencies().is_empty());
+        assert_eq!(canister.candid_gen_cfg().unwrap(), CandidGenCfg::default());
+    }
+
     #[test]
     fn test_dfx_cfg_deserialize() {
         let data = json!({
@@ -130,6 +302,100 @@ mod tests {
             canister.candid_file_path_str,
             "src/test_canister/test_canister.did"
         );
-        assert!(canister.other.get("type").is_some());
+        assert!(canister.other.contains_key("type"));
+        assert!(dfx_cfg.skipped_canisters.is_empty());
+    }
+
+    #[test]
+    fn test_dfx_cfg_deserialize_skipped_canisters() {
+        let data = json!({
+            "canisters": {
+                "test_canister": {
+                    "candid": "src/test_canister/test_canister.did",
+                    "type": "rust"
+                },
+                "no_candid_canister": {
+                    "package": "no_candid_canister",
+                    "type": "rust"
+                },
+                "non_rust_canister": {
+                    "type": "motoko"
+                }
+            }
+        });
+
+        let dfx_cfg: DfxCfg = serde_json::from_value(data).expect("Failed to deserialize");
+
+        assert_eq!(dfx_cfg.canisters.0.len(), 1);
+        assert_eq!(
+            dfx_cfg.canisters.0["no_candid_canister"].candid_file_path_str,
+            ""
+        );
+        assert_eq!(
+            dfx_cfg.skipped_canisters,
+            vec![(
+                "test_canister".to_string(),
+                "missing field `package`".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_dfx_cfg_from_file_invalid_json() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let dfx_path = temp_dir.path().join("dfx.json");
+        std::fs::write(
+            &dfx_path,
+            "{\n    \"canisters\": {\n        \"test\": {\n            \"type\": \"rust\"\n            \"package\": \"test\"\n",
+        )
+        .expect("Failed to write dfx.json");
+
+        let result = DfxCfg::from_file(&dfx_path);
+        let error = result.unwrap_err();
+        assert_eq!(error.exit_code(), 2);
+        let message = error.to_string();
+        assert!(message.contains(&format!(
+            "{}:5:13: expected `,` or `}}`",
+            dfx_path.display()
+        )));
+        assert!(message.contains("5 |             \"package\": \"test\""));
+    }
+
+    #[test]
+    fn test_dfx_cfg_from_file_candid_gen_cfg() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let dfx_path = temp_dir.path().join("dfx.json");
+        let dfx_json = json!({
+            "canisters": {
+                "wallet": {
+                    "type": "rust",
+                    "package": "wallet",
+                    "candid": "src/wallet/wallet.did",
+                    "candid_gen": { "output": "candid/wallet.did" }
+                }
+            }
+        });
+        std::fs::write(&dfx_path, dfx_json.to_string()).expect("Failed to write dfx.json");
+        let dfx_cfg = DfxCfg::from_file(&dfx_path).unwrap();
+        assert_eq!(
+            dfx_cfg.canisters.0["wallet"].candid_file_path_str,
+            "candid/wallet.did"
+        );
+
+        let dfx_json = json!({
+            "canisters": {
+                "wallet": {
+                    "type": "rust",
+                    "package": "wallet",
+                    "candid_gen": { "skip": true, "rust_binding": "src/clients" }
+                }
+            }
+        });
+        std::fs::write(&dfx_path, dfx_json.to_string()).expect("Failed to write dfx.json");
+        let error = DfxCfg::from_file(&dfx_path).unwrap_err();
+        assert_eq!(error.exit_code(), 2);
+        assert!(error.to_string().contains(
+            "canister 'wallet': Invalid 'candid_gen' settings: unknown field `rust_binding`"
+        ));
     }
 }
@@ -0,0 +1,95 @@
+use core::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandidGenError {
+    MissingTool(String),
+    Config(String),
+    Build { canister: String, message: String },
+    Extraction { canister: String, message: String },
+    Write { path: String, message: String },
+    Outdated { canister: String, path: String },
+    Breaking { canister: String },
+    Failed { canisters: Vec<String> },
+}
+
+impl CandidGenError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CandidGenError::Config(_) => 2,
+            CandidGenError::MissingTool(_) => 3,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for CandidGenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CandidGenError::MissingTool(message) => write!(f, "Missing tool: {}", message),
+            CandidGenError::Config(message) => write!(f, "Configuration error: {}", message),
+            CandidGenError::Build { canister, message } => {
+                write!(
+                    f,
+                    "Failed to build the canister '{}': {}",
+                    canister, message
+                )
+            }
+            CandidGenError::Extraction { canister, message } => write!(
+                f,
+                "Failed to extract candid for the canister '{}': {}",
+                canister, message
+            ),
+            CandidGenError::Write { path, message } => {
+                write!(f, "Failed to write {}: {}", path, message)
+            }
+            CandidGenError::Outdated { canister, path } => write!(
+                f,
+                "Canister '{}' candid file {} is out of date",
+                canister, path
+            ),
+            CandidGenError::Breaking { canister } => write!(
+                f,
+                "Canister '{}' candid interface has breaking changes",
+                canister
+            ),
+            CandidGenError::Failed { canisters } => write!(
+                f,
+                "{} canister(s) failed: {}",
+                canisters.len(),
+                canisters.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CandidGenError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candid_gen_error_exit_code() {
+        assert_eq!(CandidGenError::Config("".to_string()).exit_code(), 2);
+        assert_eq!(CandidGenError::MissingTool("".to_string()).exit_code(), 3);
+        assert_eq!(
+            CandidGenError::Failed {
+                canisters: vec!["test".to_string()]
+            }
+            .exit_code(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_candid_gen_error_display() {
+        let error = CandidGenError::Build {
+            canister: "test".to_string(),
+            message: "compilation failed".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Failed to build the canister 'test': compilation failed"
+        );
+    }
+}
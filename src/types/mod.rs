@@ -1,2 +1,9 @@
+pub mod build_options;
+pub mod candid_cache;
+pub mod candid_gen_cfg;
+pub mod canister_selection;
 pub mod canisters;
 pub mod dfx_cfg;
+pub mod error;
+pub mod progress;
+pub mod settings;
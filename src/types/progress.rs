@@ -0,0 +1,115 @@
+use std::sync::Mutex;
+
+use crate::functions::print_message::CapturedMessage;
+
+const STATUS_LINE_MAX_WIDTH: usize = 80;
+
+#[derive(Debug, Default)]
+struct ProgressState {
+    running: Vec<String>,
+    finished: usize,
+}
+
+/// Per-canister progress of the concurrent extraction. On a terminal, a status line with the
+/// running canisters is kept at the bottom of the output; otherwise only plain lines are printed.
+#[derive(Debug)]
+pub struct Progress {
+    total: usize,
+    is_terminal: bool,
+    state: Mutex<ProgressState>,
+}
+
+impl Progress {
+    pub fn new(total: usize, is_terminal: bool) -> Self {
+        Progress {
+            total,
+            is_terminal,
+            state: Mutex::new(ProgressState::default()),
+        }
+    }
+
+    pub fn start(&self, canister_name: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.running.push(canister_name.to_string());
+        if self.is_terminal {
+            eprint!("\r\x1b[2K{}", self.status_line(&state));
+        }
+    }
+
+    pub fn finish(
+        &self,
+        canister_name: &str,
+        captured_messages: &[CapturedMessage],
+        error: Option<&anyhow::Error>,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.running.retain(|running| running != canister_name);
+        state.finished += 1;
+        if self.is_terminal {
+            eprint!("\r\x1b[2K");
+        }
+        for captured_message in captured_messages {
+            match captured_message {
+                CapturedMessage::Stdout(message) => println!("{}", message),
+                CapturedMessage::Stderr(message) => eprintln!("{}", message),
+            }
+        }
+        match error {
+            Some(e) => eprintln!(
+                "candid-gen error: [{}/{}] {:#}",
+                state.finished, self.total, e
+            ),
+            None => println!(
+                "candid-gen: [{}/{}] Canister '{}' done.",
+                state.finished, self.total, canister_name
+            ),
+        }
+        if self.is_terminal && !state.running.is_empty() {
+            eprint!("{}", self.status_line(&state));
+        }
+    }
+
+    fn status_line(&self, state: &ProgressState) -> String {
+        let status_line = format!(
+            "candid-gen: [{}/{}] extracting {}",
+            state.finished,
+            self.total,
+            state.running.join(", ")
+        );
+        match status_line.char_indices().nth(STATUS_LINE_MAX_WIDTH - 3) {
+            Some((index, _)) => format!("{}...", &status_line[..index]),
+            None => status_line,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_status_line() {
+        let progress = Progress::new(3, false);
+        progress.start("counter");
+        progress.start("ledger");
+        progress.finish("counter", &[], None);
+        let state = progress.state.lock().unwrap();
+        assert_eq!(state.running, vec!["ledger"]);
+        assert_eq!(
+            progress.status_line(&state),
+            "candid-gen: [1/3] extracting ledger"
+        );
+    }
+
+    #[test]
+    fn test_progress_status_line_truncated() {
+        let progress = Progress::new(20, false);
+        let state = ProgressState {
+            running: (0..10).map(|i| format!("canister_{}", i)).collect(),
+            finished: 0,
+        };
+        let status_line = progress.status_line(&state);
+        assert_eq!(status_line.chars().count(), STATUS_LINE_MAX_WIDTH);
+        assert!(status_line.ends_with("..."));
+    }
+}
@@ -0,0 +1,450 @@
+use core::fmt;
+use std::{collections::BTreeMap, path::PathBuf};
+
+use serde::Deserialize;
+
+use super::{build_options::BuildOptions, candid_gen_cfg::CandidGenCfg, error::CandidGenError};
+use crate::{BUILD_PROFILE, CANDID_PATH_TEMPLATE, ENV_VARS_PREFIX, SETTINGS_MANIFEST_TABLE};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingSource {
+    CommandLine,
+    Env,
+    File(PathBuf),
+    CargoManifest(PathBuf),
+    DfxJson,
+    Default,
+}
+
+impl SettingSource {
+    /// Describes where the setting `key` came from, naming the environment variable for `Env`.
+    pub fn describe(&self, key: &str) -> String {
+        match self {
+            SettingSource::Env => env_var_name(key),
+            source => source.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingSource::CommandLine => write!(f, "command line"),
+            SettingSource::Env => write!(f, "environment"),
+            SettingSource::File(path) => write!(f, "{}", path.display()),
+            SettingSource::CargoManifest(path) => {
+                write!(f, "{} [{}]", path.display(), SETTINGS_MANIFEST_TABLE)
+            }
+            SettingSource::DfxJson => write!(f, "dfx.json"),
+            SettingSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+fn env_var_name(key: &str) -> String {
+    format!(
+        "{}{}",
+        ENV_VARS_PREFIX,
+        key.replace('.', "_").to_uppercase()
+    )
+}
+
+/// Settings of a single source. The unset ones fall through to the next source.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SettingsLayer {
+    pub candid_path_template: Option<String>,
+    pub deny_breaking: Option<bool>,
+    pub strict: Option<bool>,
+    pub declarations: Option<bool>,
+    pub profile: Option<String>,
+    pub features: Option<Vec<String>>,
+    pub no_default_features: Option<bool>,
+    pub locked: Option<bool>,
+    pub offline: Option<bool>,
+    pub env: Option<BTreeMap<String, String>>,
+}
+
+impl SettingsLayer {
+    /// Reads the `CANDID_GEN_*` variables, e.g. `CANDID_GEN_PROFILE`, `CANDID_GEN_FEATURES=a,b`
+    /// or `CANDID_GEN_ENV_RUSTFLAGS` for the `RUSTFLAGS` of `cargo build`.
+    pub fn from_env_vars(
+        env_vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, CandidGenError> {
+        let mut settings_layer = SettingsLayer::default();
+        for (name, value) in env_vars {
+            let Some(key) = name.strip_prefix(ENV_VARS_PREFIX) else {
+                continue;
+            };
+            let parse_bool = || match value.to_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => Ok(Some(true)),
+                "0" | "false" | "no" | "off" | "" => Ok(Some(false)),
+                _ => Err(CandidGenError::Config(format!(
+                    "{}: invalid boolean '{}'",
+                    name, value
+                ))),
+            };
+            match key {
+                "CANDID_PATH_TEMPLATE" => settings_layer.candid_path_template = Some(value.clone()),
+                "DENY_BREAKING" => settings_layer.deny_breaking = parse_bool()?,
+                "STRICT" => settings_layer.strict = parse_bool()?,
+                "DECLARATIONS" => settings_layer.declarations = parse_bool()?,
+                "PROFILE" => settings_layer.profile = Some(value.clone()),
+                "FEATURES" => {
+                    settings_layer.features = Some(
+                        value
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|feature| !feature.is_empty())
+                            .map(str::to_string)
+                            .collect(),
+                    )
+                }
+                "NO_DEFAULT_FEATURES" => settings_layer.no_default_features = parse_bool()?,
+                "LOCKED" => settings_layer.locked = parse_bool()?,
+                "OFFLINE" => settings_layer.offline = parse_bool()?,
+                _ => match key.strip_prefix("ENV_") {
+                    Some(env_key) if !env_key.is_empty() => {
+                        settings_layer
+                            .env
+                            .get_or_insert_with(BTreeMap::new)
+                            .insert(env_key.to_string(), value.clone());
+                    }
+                    _ => {
+                        return Err(CandidGenError::Config(format!(
+                            "Unknown environment variable {}",
+                            name
+                        )))
+                    }
+                },
+            }
+        }
+        Ok(settings_layer)
+    }
+}
+
+impl From<&CandidGenCfg> for SettingsLayer {
+    fn from(candid_gen_cfg: &CandidGenCfg) -> Self {
+        SettingsLayer {
+            declarations: candid_gen_cfg.declarations,
+            profile: candid_gen_cfg.profile.clone(),
+            features: Some(candid_gen_cfg.features.clone()).filter(|features| !features.is_empty()),
+            no_default_features: candid_gen_cfg.no_default_features,
+            locked: candid_gen_cfg.locked,
+            offline: candid_gen_cfg.offline,
+            env: Some(candid_gen_cfg.env.clone()).filter(|env| !env.is_empty()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: SettingSource,
+}
+
+impl<T: ToString> Setting<T> {
+    fn displayed(self) -> Setting<String> {
+        Setting {
+            value: self.value.to_string(),
+            source: self.source,
+        }
+    }
+}
+
+/// Settings layers, from the highest to the lowest precedence:
+/// command line, `CANDID_GEN_*` variables, configuration file and the canister's dfx.json keys.
+/// Features are merged from every layer, and so are the environment variables, by key.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Settings(pub Vec<(SettingSource, SettingsLayer)>);
+
+impl Settings {
+    /// Adds the `candid_gen` settings of a canister as the lowest layer.
+    pub fn with_canister(&self, candid_gen_cfg: &CandidGenCfg) -> Self {
+        let mut settings = self.clone();
+        settings
+            .0
+            .push((SettingSource::DfxJson, SettingsLayer::from(candid_gen_cfg)));
+        settings
+    }
+
+    fn resolve<T: Clone>(&self, field: fn(&SettingsLayer) -> &Option<T>) -> Option<Setting<T>> {
+        self.0.iter().find_map(|(source, settings_layer)| {
+            field(settings_layer).as_ref().map(|value| Setting {
+                value: value.clone(),
+                source: source.clone(),
+            })
+        })
+    }
+
+    fn resolve_or<T: Clone>(
+        &self,
+        field: fn(&SettingsLayer) -> &Option<T>,
+        default: T,
+    ) -> Setting<T> {
+        self.resolve(field).unwrap_or(Setting {
+            value: default,
+            source: SettingSource::Default,
+        })
+    }
+
+    pub fn candid_path_template(&self) -> Setting<String> {
+        self.resolve_or(
+            |settings_layer| &settings_layer.candid_path_template,
+            CANDID_PATH_TEMPLATE.to_string(),
+        )
+    }
+
+    pub fn deny_breaking(&self) -> Setting<bool> {
+        self.resolve_or(|settings_layer| &settings_layer.deny_breaking, false)
+    }
+
+    pub fn strict(&self) -> Setting<bool> {
+        self.resolve_or(|settings_layer| &settings_layer.strict, false)
+    }
+
+    pub fn declarations(&self) -> Setting<bool> {
+        self.resolve_or(|settings_layer| &settings_layer.declarations, false)
+    }
+
+    pub fn profile(&self) -> Setting<String> {
+        self.resolve_or(
+            |settings_layer| &settings_layer.profile,
+            BUILD_PROFILE.to_string(),
+        )
+    }
+
+    pub fn features(&self) -> Vec<Setting<String>> {
+        let mut features: Vec<Setting<String>> = Vec::new();
+        for (source, settings_layer) in self.0.iter() {
+            for feature in settings_layer.features.iter().flatten() {
+                if !features.iter().any(|setting| &setting.value == feature) {
+                    features.push(Setting {
+                        value: feature.clone(),
+                        source: source.clone(),
+                    });
+                }
+            }
+        }
+        features
+    }
+
+    pub fn no_default_features(&self) -> Setting<bool> {
+        self.resolve_or(|settings_layer| &settings_layer.no_default_features, false)
+    }
+
+    pub fn locked(&self) -> Setting<bool> {
+        self.resolve_or(|settings_layer| &settings_layer.locked, false)
+    }
+
+    pub fn offline(&self) -> Setting<bool> {
+        self.resolve_or(|settings_layer| &settings_layer.offline, false)
+    }
+
+    pub fn env(&self) -> BTreeMap<String, Setting<String>> {
+        let mut env = BTreeMap::new();
+        for (source, settings_layer) in self.0.iter().rev() {
+            for (key, value) in settings_layer.env.iter().flatten() {
+                env.insert(
+                    key.clone(),
+                    Setting {
+                        value: value.clone(),
+                        source: source.clone(),
+                    },
+                );
+            }
+        }
+        env
+    }
+
+    pub fn build_options(&self) -> BuildOptions {
+        BuildOptions {
+            profile: self
+                .resolve(|settings_layer| &settings_layer.profile)
+                .map(|setting| setting.value),
+            features: self
+                .features()
+                .into_iter()
+                .map(|setting| setting.value)
+                .collect(),
+            no_default_features: self.no_default_features().value,
+            locked: self.locked().value,
+            offline: self.offline().value,
+            env: self
+                .env()
+                .into_iter()
+                .map(|(key, setting)| (key, setting.value))
+                .collect(),
+            dfx_artifact: None,
+            manifest_path: None,
+        }
+    }
+
+    /// Every effective setting as `(key, value, source)`, for `config explain`.
+    pub fn explain(&self) -> Vec<(String, String, String)> {
+        let mut rows: Vec<(String, Setting<String>)> = vec![
+            (
+                "candid_path_template".to_string(),
+                self.candid_path_template(),
+            ),
+            (
+                "deny_breaking".to_string(),
+                self.deny_breaking().displayed(),
+            ),
+            ("strict".to_string(), self.strict().displayed()),
+            ("declarations".to_string(), self.declarations().displayed()),
+            ("profile".to_string(), self.profile()),
+        ];
+        let features = self.features();
+        if features.is_empty() {
+            rows.push((
+                "features".to_string(),
+                Setting {
+                    value: String::new(),
+                    source: SettingSource::Default,
+                },
+            ));
+        }
+        for feature in features {
+            rows.push(("features".to_string(), feature));
+        }
+        rows.push((
+            "no_default_features".to_string(),
+            self.no_default_features().displayed(),
+        ));
+        rows.push(("locked".to_string(), self.locked().displayed()));
+        rows.push(("offline".to_string(), self.offline().displayed()));
+        for (key, setting) in self.env() {
+            rows.push((format!("env.{}", key), setting));
+        }
+        rows.into_iter()
+            .map(|(key, setting)| {
+                let source = setting.source.describe(&key);
+                (key, setting.value, source)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_vars(env_vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        env_vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_settings_layer_from_env_vars() {
+        let settings_layer = SettingsLayer::from_env_vars(env_vars(&[
+            ("HOME", "/root"),
+            ("CANDID_GEN_PROFILE", "canister"),
+            ("CANDID_GEN_FEATURES", "export-candid, metrics"),
+            ("CANDID_GEN_LOCKED", "true"),
+            ("CANDID_GEN_OFFLINE", "0"),
+            ("CANDID_GEN_ENV_RUSTFLAGS", "-C opt-level=z"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            settings_layer,
+            SettingsLayer {
+                profile: Some("canister".to_string()),
+                features: Some(vec!["export-candid".to_string(), "metrics".to_string()]),
+                locked: Some(true),
+                offline: Some(false),
+                env: Some(BTreeMap::from([(
+                    "RUSTFLAGS".to_string(),
+                    "-C opt-level=z".to_string()
+                )])),
+                ..Default::default()
+            }
+        );
+        assert!(SettingsLayer::from_env_vars(env_vars(&[("CANDID_GEN_LOCKED", "maybe")])).is_err());
+        assert!(SettingsLayer::from_env_vars(env_vars(&[("CANDID_GEN_PROFLE", "dev")])).is_err());
+    }
+
+    #[test]
+    fn test_settings_precedence() {
+        let settings = Settings(vec![
+            (
+                SettingSource::CommandLine,
+                SettingsLayer {
+                    features: Some(vec!["metrics".to_string()]),
+                    ..Default::default()
+                },
+            ),
+            (
+                SettingSource::Env,
+                SettingsLayer {
+                    profile: Some("dev".to_string()),
+                    env: Some(BTreeMap::from([(
+                        "RUSTFLAGS".to_string(),
+                        "-C debuginfo=0".to_string(),
+                    )])),
+                    ..Default::default()
+                },
+            ),
+            (
+                SettingSource::File(PathBuf::from("candid-gen.toml")),
+                SettingsLayer {
+                    profile: Some("canister".to_string()),
+                    locked: Some(false),
+                    deny_breaking: Some(true),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let candid_gen_cfg = CandidGenCfg {
+            features: vec!["export-candid".to_string(), "metrics".to_string()],
+            locked: Some(true),
+            offline: Some(true),
+            env: BTreeMap::from([
+                ("RUSTFLAGS".to_string(), "-C opt-level=z".to_string()),
+                ("FOO".to_string(), "bar".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let settings = settings.with_canister(&candid_gen_cfg);
+
+        assert_eq!(
+            settings.profile(),
+            Setting {
+                value: "dev".to_string(),
+                source: SettingSource::Env
+            }
+        );
+        assert!(settings.deny_breaking().value);
+        assert_eq!(settings.strict().source, SettingSource::Default);
+        let build_options = settings.build_options();
+        assert_eq!(build_options.features, vec!["metrics", "export-candid"]);
+        assert!(!build_options.locked && build_options.offline);
+        assert_eq!(build_options.env["RUSTFLAGS"], "-C debuginfo=0");
+        assert_eq!(build_options.env["FOO"], "bar");
+
+        let explain = settings.explain();
+        assert!(explain.contains(&(
+            "profile".to_string(),
+            "dev".to_string(),
+            "CANDID_GEN_PROFILE".to_string()
+        )));
+        assert!(explain.contains(&(
+            "locked".to_string(),
+            "false".to_string(),
+            "candid-gen.toml".to_string()
+        )));
+        assert!(explain.contains(&(
+            "env.FOO".to_string(),
+            "bar".to_string(),
+            "dfx.json".to_string()
+        )));
+        assert!(explain.contains(&(
+            "features".to_string(),
+            "metrics".to_string(),
+            "command line".to_string()
+        )));
+    }
+}
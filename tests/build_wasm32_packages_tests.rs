@@ -0,0 +1,68 @@
+use std::{
+    collections::HashMap,
+    env::set_current_dir,
+    fs::{create_dir_all, write},
+    path::Path,
+};
+
+use anyhow::Result;
+use candid_gen::{
+    functions::build_wasm32_packages::build_wasm32_packages,
+    types::{build_options::BuildOptions, dfx_cfg::RustCanisterCfg, error::CandidGenError},
+};
+use serial_test::serial;
+use tempfile::tempdir;
+
+fn create_package(workspace_dir: &Path, package: &str, lib_rs: &str) -> Result<RustCanisterCfg> {
+    let package_dir = workspace_dir.join(package);
+    create_dir_all(package_dir.join("src"))?;
+    write(
+        package_dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n",
+            package
+        ),
+    )?;
+    write(package_dir.join("src/lib.rs"), lib_rs)?;
+    Ok(RustCanisterCfg {
+        package: package.to_string(),
+        candid_file_path_str: format!("{}/{}.did", package, package),
+        other: HashMap::new(),
+    })
+}
+
+#[test]
+#[serial]
+fn test_build_wasm32_packages_partial_failure() -> Result<()> {
+    let temp_dir = tempdir()?;
+    write(
+        temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"ok-canister\", \"broken_canister\"]\nresolver = \"2\"\n",
+    )?;
+    let ok_canister = create_package(
+        temp_dir.path(),
+        "ok-canister",
+        "#[no_mangle]\npub extern \"C\" fn answer() -> i32 { 42 }\n",
+    )?;
+    let broken_canister = create_package(
+        temp_dir.path(),
+        "broken_canister",
+        "pub fn broken() -> i32 { \"not a number\" }\n",
+    )?;
+    set_current_dir(&temp_dir)?;
+
+    let build_results =
+        build_wasm32_packages(&[&ok_canister, &broken_canister], &BuildOptions::default());
+
+    let ok_wasm_path_str = build_results["ok-canister"].as_ref().unwrap();
+    assert!(ok_wasm_path_str.ends_with("wasm32-unknown-unknown/release/ok_canister.wasm"));
+    assert!(Path::new(ok_wasm_path_str).is_file());
+    match &build_results["broken_canister"] {
+        Err(CandidGenError::Build { canister, message }) => {
+            assert_eq!(canister, "broken_canister");
+            assert!(message.contains("mismatched types"), "{}", message);
+        }
+        result => panic!("Unexpected build result: {:?}", result),
+    }
+    Ok(())
+}
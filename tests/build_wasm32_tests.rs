@@ -1,8 +1,9 @@
 use anyhow::{anyhow, Context, Result};
-use candid_gen::functions::build_wasm32::build_wasm32;
+use candid_gen::functions::build_wasm32_packages::build_wasm32_packages;
+use candid_gen::types::build_options::BuildOptions;
 use candid_gen::types::canisters::Canisters;
 use candid_gen::types::dfx_cfg::{DfxCfg, RustCanisterCfg};
-use candid_gen::BUILD_OUTPUT_DIR;
+use candid_gen::{BUILD_PROFILE, BUILD_TARGET};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::env::{current_dir, set_current_dir};
@@ -28,7 +29,10 @@ fn test_mock_project_dir() -> Result<()> {
 fn test_build_wasm32_success() -> Result<()> {
     let project_root = MOCK_PROJECT_DIR.lock().unwrap();
     set_current_dir(&*project_root)?;
-    let build_output_path = project_root.join(BUILD_OUTPUT_DIR);
+    let build_output_path = project_root
+        .join("target")
+        .join(BUILD_TARGET)
+        .join(BUILD_PROFILE);
     let dfx_path = project_root.join("dfx.json");
     let dfx_json = read_to_string(&dfx_path).context("Failed to read dfx.json file")?;
     let dfx_cfg: DfxCfg = serde_json::from_str(&dfx_json).unwrap();
@@ -38,7 +42,9 @@ fn test_build_wasm32_success() -> Result<()> {
         if let Err(e) = remove_file(&wasm_file) {
             eprintln!("{} already deleted: {}", &wasm_file.display(), e);
         };
-        build_wasm32(canister)?;
+        build_wasm32_packages(&[canister], &BuildOptions::default())
+            .remove(&canister.package)
+            .ok_or_else(|| anyhow!("No build result for {}.", canister_name))??;
         assert!(wasm_file.exists(), "Build output should exist");
         remove_file(&wasm_file)?;
     } else {
@@ -51,7 +57,10 @@ fn test_build_wasm32_success() -> Result<()> {
 fn test_build_wasm32_failure() -> Result<()> {
     let project_root = MOCK_PROJECT_DIR.lock().unwrap();
     set_current_dir(&*project_root)?;
-    let build_output_path = project_root.join(BUILD_OUTPUT_DIR);
+    let build_output_path = project_root
+        .join("target")
+        .join(BUILD_TARGET)
+        .join(BUILD_PROFILE);
 
     let canister = RustCanisterCfg {
         package: "nonexistent_canister".to_string(),
@@ -60,7 +69,9 @@ fn test_build_wasm32_failure() -> Result<()> {
     };
     let canister_name = &canister.package;
     let wasm_file = build_output_path.join(format!("{}.wasm", canister_name));
-    let build_result = build_wasm32(&canister);
+    let build_result = build_wasm32_packages(&[&canister], &BuildOptions::default())
+        .remove(canister_name)
+        .ok_or_else(|| anyhow!("No build result for {}.", canister_name))?;
     assert!(!wasm_file.exists(), "Build output should NOT exist");
     assert!(build_result.is_err(), "Build result must be an error");
 
@@ -0,0 +1,62 @@
+mod common;
+
+use std::fs::write;
+
+use anyhow::Result;
+use candid_gen::{functions::check_candid::check_candid, types::dfx_cfg::RustCanisterCfg};
+use common::{create_temp_project_with_wasm, mock_wasm_path, CANDID};
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn test_check_candid_up_to_date() -> Result<()> {
+    let canister = RustCanisterCfg::new("test");
+    let temp_dir = create_temp_project_with_wasm(&canister)?;
+    write(temp_dir.path().join(&canister.candid_file_path_str), CANDID)?;
+    assert!(check_candid(
+        temp_dir.path(),
+        &canister,
+        &mock_wasm_path(temp_dir.path(), &canister).to_string_lossy(),
+        false
+    )?);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_check_candid_outdated() -> Result<()> {
+    let canister = RustCanisterCfg::new("test");
+    let temp_dir = create_temp_project_with_wasm(&canister)?;
+    let candid_file_path = temp_dir.path().join(&canister.candid_file_path_str);
+    write(&candid_file_path, "service : {}")?;
+    assert!(!check_candid(
+        temp_dir.path(),
+        &canister,
+        &mock_wasm_path(temp_dir.path(), &canister).to_string_lossy(),
+        false
+    )?);
+    assert_eq!(
+        std::fs::read_to_string(&candid_file_path)?,
+        "service : {}",
+        "Check mode must not write the candid file"
+    );
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_check_candid_missing_file() -> Result<()> {
+    let canister = RustCanisterCfg::new("test");
+    let temp_dir = create_temp_project_with_wasm(&canister)?;
+    assert!(!check_candid(
+        temp_dir.path(),
+        &canister,
+        &mock_wasm_path(temp_dir.path(), &canister).to_string_lossy(),
+        false
+    )?);
+    assert!(!temp_dir
+        .path()
+        .join(&canister.candid_file_path_str)
+        .exists());
+    Ok(())
+}
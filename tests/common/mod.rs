@@ -0,0 +1,65 @@
+#![allow(dead_code)]
+
+use std::{
+    env::set_current_dir,
+    fs::{create_dir_all, write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use candid_gen::{types::dfx_cfg::RustCanisterCfg, BUILD_PROFILE, BUILD_TARGET};
+use tempfile::{tempdir, TempDir};
+
+pub const CANDID: &str = "service : { greet : (text) -> (text) query }";
+
+/// A canister module exporting `candid` through `get_candid_pointer`, like `ic_cdk::export_candid!`.
+pub fn mock_canister_wasm(candid: &str) -> Result<Vec<u8>> {
+    Ok(wat::parse_str(format!(
+        r#"(module
+            (import "ic0" "msg_reply" (func $msg_reply))
+            (import "ic0" "msg_arg_data_size" (func $msg_arg_data_size (result i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "{}\00")
+            (func (export "get_candid_pointer") (result i32)
+                i32.const 16)
+            (func (export "canister_query greet")
+                call $msg_reply))"#,
+        candid
+    ))?)
+}
+
+/// The cargo package of `canister` with its built wasm and its candid directory,
+/// set as the current directory.
+pub fn create_temp_project_with_wasm(canister: &RustCanisterCfg) -> Result<TempDir> {
+    let temp_dir = create_temp_cargo_package(&canister.package, "")?;
+    let wasm_path = mock_wasm_path(temp_dir.path(), canister);
+    create_dir_all(wasm_path.parent().unwrap())?;
+    write(&wasm_path, mock_canister_wasm(CANDID)?)?;
+    create_dir_all(temp_dir.path().join(format!("src/{}", canister.package)))?;
+    Ok(temp_dir)
+}
+
+/// The wasm written by `create_temp_project_with_wasm`.
+pub fn mock_wasm_path(project_root: &Path, canister: &RustCanisterCfg) -> PathBuf {
+    project_root
+        .join("target")
+        .join(BUILD_TARGET)
+        .join(BUILD_PROFILE)
+        .join(format!("{}.wasm", canister.package))
+}
+
+/// A cargo package with an empty `src/lib.rs`, set as the current directory.
+pub fn create_temp_cargo_package(package: &str, lib_section: &str) -> Result<TempDir> {
+    let temp_dir = tempdir()?;
+    create_dir_all(temp_dir.path().join("src"))?;
+    write(
+        temp_dir.path().join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n{}",
+            package, lib_section
+        ),
+    )?;
+    write(temp_dir.path().join("src/lib.rs"), "")?;
+    set_current_dir(&temp_dir)?;
+    Ok(temp_dir)
+}
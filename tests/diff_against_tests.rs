@@ -0,0 +1,116 @@
+mod common;
+
+use std::{fs::write, path::Path};
+
+use anyhow::Result;
+use candid_gen::{
+    functions::{diff_against::diff_against, run_command::run_command},
+    types::dfx_cfg::RustCanisterCfg,
+};
+use common::{create_temp_project_with_wasm, mock_wasm_path, CANDID};
+use serial_test::serial;
+use tempfile::TempDir;
+
+fn create_temp_repo_with_wasm(canister: &RustCanisterCfg) -> Result<TempDir> {
+    let temp_dir = create_temp_project_with_wasm(canister)?;
+    run_command("git init --quiet")?;
+    Ok(temp_dir)
+}
+
+fn commit_candid(project_root: &Path, canister: &RustCanisterCfg, candid: &str) -> Result<()> {
+    write(project_root.join(&canister.candid_file_path_str), candid)?;
+    run_command(
+        "git add --all && \
+            git -c user.name=test -c user.email=test@test commit --quiet -m candid",
+    )?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_diff_against_unchanged() -> Result<()> {
+    let canister = RustCanisterCfg::new("test");
+    let temp_dir = create_temp_repo_with_wasm(&canister)?;
+    commit_candid(temp_dir.path(), &canister, CANDID)?;
+    assert!(diff_against(
+        temp_dir.path(),
+        &canister,
+        &mock_wasm_path(temp_dir.path(), &canister).to_string_lossy(),
+        false,
+        "HEAD"
+    )?);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_diff_against_compatible_change() -> Result<()> {
+    let canister = RustCanisterCfg::new("test");
+    let temp_dir = create_temp_repo_with_wasm(&canister)?;
+    commit_candid(temp_dir.path(), &canister, "service : {}")?;
+    assert!(diff_against(
+        temp_dir.path(),
+        &canister,
+        &mock_wasm_path(temp_dir.path(), &canister).to_string_lossy(),
+        false,
+        "HEAD"
+    )?);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_diff_against_breaking_change() -> Result<()> {
+    let canister = RustCanisterCfg::new("test");
+    let temp_dir = create_temp_repo_with_wasm(&canister)?;
+    commit_candid(
+        temp_dir.path(),
+        &canister,
+        "service : { greet : (text) -> (text) query; inc : () -> () }",
+    )?;
+    assert!(!diff_against(
+        temp_dir.path(),
+        &canister,
+        &mock_wasm_path(temp_dir.path(), &canister).to_string_lossy(),
+        false,
+        "HEAD"
+    )?);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_diff_against_missing_at_ref() -> Result<()> {
+    let canister = RustCanisterCfg::new("test");
+    let temp_dir = create_temp_repo_with_wasm(&canister)?;
+    write(temp_dir.path().join("README.md"), "test")?;
+    run_command(
+        "git add README.md && \
+            git -c user.name=test -c user.email=test@test commit --quiet -m readme",
+    )?;
+    assert!(diff_against(
+        temp_dir.path(),
+        &canister,
+        &mock_wasm_path(temp_dir.path(), &canister).to_string_lossy(),
+        false,
+        "HEAD"
+    )?);
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_diff_against_invalid_ref() -> Result<()> {
+    let canister = RustCanisterCfg::new("test");
+    let temp_dir = create_temp_repo_with_wasm(&canister)?;
+    commit_candid(temp_dir.path(), &canister, CANDID)?;
+    assert!(diff_against(
+        temp_dir.path(),
+        &canister,
+        &mock_wasm_path(temp_dir.path(), &canister).to_string_lossy(),
+        false,
+        "no-such-ref"
+    )
+    .is_err());
+    Ok(())
+}
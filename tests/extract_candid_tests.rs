@@ -0,0 +1,34 @@
+mod common;
+
+use anyhow::Result;
+use candid_gen::functions::extract_candid::extract_candid;
+use candid_parser::{Error, IDLProg};
+use common::{mock_canister_wasm, CANDID};
+
+#[test]
+fn test_extract_candid_success() -> Result<()> {
+    let candid = extract_candid(&mock_canister_wasm(CANDID)?)?;
+    assert_eq!(candid, CANDID);
+    let ast: Result<IDLProg, Error> = candid.parse();
+    assert!(ast.is_ok(), "Extracted candid should be valid");
+    Ok(())
+}
+
+#[test]
+fn test_extract_candid_failure_no_candid_export() {
+    let wasm = wat::parse_str(r#"(module (memory (export "memory") 1))"#).unwrap();
+    let result = extract_candid(&wasm);
+    assert!(
+        result.is_err(),
+        "Extraction without 'get_candid_pointer' must fail"
+    );
+}
+
+#[test]
+fn test_extract_candid_failure_invalid_wasm() {
+    let result = extract_candid(b"not a wasm module");
+    assert!(
+        result.is_err(),
+        "Extraction from an invalid module must fail"
+    );
+}
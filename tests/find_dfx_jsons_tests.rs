@@ -0,0 +1,39 @@
+use std::fs::{create_dir_all, write};
+
+use anyhow::Result;
+use candid_gen::functions::find_dfx_jsons::find_dfx_jsons;
+use tempfile::tempdir;
+
+#[test]
+fn test_find_dfx_jsons() -> Result<()> {
+    let temp_dir = tempdir()?;
+    for dfx_dir in [
+        "apps/wallet",
+        "apps/ledger",
+        "apps/ledger/node_modules/some_package",
+        "apps/ledger/target/package",
+        "apps/ledger/.dfx/local",
+        "tools",
+    ] {
+        create_dir_all(temp_dir.path().join(dfx_dir))?;
+    }
+    for dfx_path in [
+        "apps/wallet/dfx.json",
+        "apps/ledger/dfx.json",
+        "apps/ledger/node_modules/some_package/dfx.json",
+        "apps/ledger/target/package/dfx.json",
+        "apps/ledger/.dfx/local/dfx.json",
+    ] {
+        write(temp_dir.path().join(dfx_path), "{}")?;
+    }
+
+    assert_eq!(
+        find_dfx_jsons(temp_dir.path()),
+        vec![
+            temp_dir.path().join("apps/ledger/dfx.json"),
+            temp_dir.path().join("apps/wallet/dfx.json"),
+        ]
+    );
+    assert!(find_dfx_jsons(&temp_dir.path().join("tools")).is_empty());
+    Ok(())
+}
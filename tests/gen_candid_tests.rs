@@ -9,11 +9,11 @@ use std::{
 use anyhow::{anyhow, Context, Result};
 use candid_gen::{
     functions::{
-        build_wasm32::build_wasm32, gen_candid::gen_candid,
+        build_wasm32_packages::build_wasm32_packages, gen_candid::gen_candid,
         get_candid_path_str::get_candid_path_str,
     },
-    types::{canisters::Canisters, dfx_cfg::DfxCfg},
-    BUILD_OUTPUT_DIR,
+    types::{build_options::BuildOptions, canisters::Canisters, dfx_cfg::DfxCfg},
+    BUILD_PROFILE, BUILD_TARGET,
 };
 use candid_parser::{Error, IDLProg};
 use once_cell::sync::Lazy;
@@ -36,7 +36,10 @@ fn test_mock_project_dir() -> Result<()> {
 fn test_gen_candid_success() -> Result<()> {
     let project_root = MOCK_PROJECT_DIR.lock().unwrap();
     set_current_dir(&*project_root)?;
-    let build_output_path = project_root.join(BUILD_OUTPUT_DIR);
+    let build_output_path = project_root
+        .join("target")
+        .join(BUILD_TARGET)
+        .join(BUILD_PROFILE);
     let dfx_path = project_root.join("dfx.json");
     let dfx_json = read_to_string(&dfx_path).context("Failed to read dfx.json file")?;
     let dfx_cfg: DfxCfg = serde_json::from_str(&dfx_json).unwrap();
@@ -46,12 +49,14 @@ fn test_gen_candid_success() -> Result<()> {
         if let Err(e) = remove_file(&wasm_file) {
             eprintln!("{} already deleted: {}", &wasm_file.display(), e);
         };
-        build_wasm32(canister)?;
+        let wasm_path_str = build_wasm32_packages(&[canister], &BuildOptions::default())
+            .remove(&canister.package)
+            .ok_or_else(|| anyhow!("No build result for {}.", canister_name))??;
         let candid_path = PathBuf::from_str(&get_candid_path_str(&project_root, canister)?)?;
         if let Err(e) = remove_file(&candid_path) {
             eprintln!("{} already deleted: {}", &wasm_file.display(), e);
         };
-        gen_candid(&project_root, canister)?;
+        gen_candid(&project_root, canister, &wasm_path_str, false)?;
         assert!(candid_path.exists(), "Candid file should exist");
         let candid_file = read_to_string(&candid_path).context("Failed to read candid file")?;
         let ast: Result<IDLProg, Error> = candid_file.parse();
@@ -68,7 +73,10 @@ fn test_gen_candid_failure_nonexistent_wasm() -> Result<()> {
     let project_root = MOCK_PROJECT_DIR.lock().unwrap();
     set_current_dir(&*project_root)?;
 
-    let build_output_path = project_root.join(BUILD_OUTPUT_DIR);
+    let build_output_path = project_root
+        .join("target")
+        .join(BUILD_TARGET)
+        .join(BUILD_PROFILE);
     let dfx_path = project_root.join("dfx.json");
     let dfx_json = read_to_string(&dfx_path).context("Failed to read dfx.json file")?;
     let dfx_cfg: DfxCfg = serde_json::from_str(&dfx_json).unwrap();
@@ -84,7 +92,8 @@ fn test_gen_candid_failure_nonexistent_wasm() -> Result<()> {
         if let Err(e) = remove_file(&candid_file_path) {
             eprintln!("{} already deleted: {}", &candid_file_path.display(), e);
         }
-        let gen_candid_result = gen_candid(&project_root, canister);
+        let gen_candid_result =
+            gen_candid(&project_root, canister, &wasm_file.to_string_lossy(), false);
         assert!(!candid_file_path.exists(), "Candid file should not exists");
         assert!(
             gen_candid_result.is_err(),
@@ -0,0 +1,72 @@
+mod common;
+
+use std::fs::{create_dir_all, read_to_string, write};
+
+use anyhow::Result;
+use candid_gen::{
+    functions::gen_declarations::{gen_declarations, DECLARATIONS_DEFAULT_DIR},
+    types::dfx_cfg::RustCanisterCfg,
+};
+use common::CANDID;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[test]
+fn test_gen_declarations_output_dir() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let canister: RustCanisterCfg = serde_json::from_value(json!({
+        "package": "test",
+        "candid": "src/test/test.did",
+        "type": "rust",
+        "declarations": {
+            "output": "src/frontend/declarations/test"
+        }
+    }))?;
+    create_dir_all(temp_dir.path().join("src/test"))?;
+    write(temp_dir.path().join(&canister.candid_file_path_str), CANDID)?;
+
+    gen_declarations(temp_dir.path(), "test", &canister)?;
+
+    let declarations_dir = temp_dir.path().join("src/frontend/declarations/test");
+    let did_js = read_to_string(declarations_dir.join("test.did.js"))?;
+    assert!(did_js.contains("export const idlFactory"));
+    assert!(did_js.contains("'greet'"));
+    let did_d_ts = read_to_string(declarations_dir.join("test.did.d.ts"))?;
+    assert!(did_d_ts.contains("export interface _SERVICE"));
+    let index_js = read_to_string(declarations_dir.join("index.js"))?;
+    assert!(index_js.contains("process.env.CANISTER_ID_TEST"));
+    assert!(index_js.contains("export const test = canisterId"));
+    Ok(())
+}
+
+#[test]
+fn test_gen_declarations_default_dir() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let canister: RustCanisterCfg = serde_json::from_value(json!({
+        "package": "test",
+        "candid": "src/test/test.did",
+        "type": "rust",
+        "declarations": {
+            "node_compatibility": true
+        }
+    }))?;
+    create_dir_all(temp_dir.path().join("src/test"))?;
+    write(temp_dir.path().join(&canister.candid_file_path_str), CANDID)?;
+
+    gen_declarations(temp_dir.path(), "test", &canister)?;
+
+    let declarations_dir = temp_dir.path().join(DECLARATIONS_DEFAULT_DIR).join("test");
+    assert!(declarations_dir.join("test.did.js").is_file());
+    assert!(declarations_dir.join("test.did.d.ts").is_file());
+    let index_js = read_to_string(declarations_dir.join("index.js"))?;
+    assert!(!index_js.contains("export const test = canisterId"));
+    Ok(())
+}
+
+#[test]
+fn test_gen_declarations_failure_missing_candid() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let canister = RustCanisterCfg::new("test");
+    let result = gen_declarations(temp_dir.path(), "test", &canister);
+    assert!(result.is_err());
+}
@@ -0,0 +1,53 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+};
+
+use anyhow::Result;
+use candid_gen::{
+    functions::gen_motoko_bindings::gen_motoko_bindings, types::dfx_cfg::RustCanisterCfg,
+};
+use tempfile::tempdir;
+
+const CANDID: &str = r#"
+    type Account = record { owner : principal; balance : nat };
+    service : {
+        get_account : (principal) -> (opt Account) query;
+        transfer : (principal, nat) -> ();
+    }
+"#;
+
+#[test]
+fn test_gen_motoko_bindings_success() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let canister = RustCanisterCfg::new("ledger");
+    create_dir_all(temp_dir.path().join("src/ledger"))?;
+    write(temp_dir.path().join(&canister.candid_file_path_str), CANDID)?;
+
+    gen_motoko_bindings(
+        temp_dir.path(),
+        "ledger",
+        &canister,
+        Path::new("src/motoko_app/bindings"),
+    )?;
+
+    let bindings = read_to_string(temp_dir.path().join("src/motoko_app/bindings/ledger.mo"))?;
+    assert!(bindings.contains("module {"));
+    assert!(bindings.contains("public type Account"));
+    assert!(bindings.contains("public type Self = actor {"));
+    assert!(bindings.contains("get_account : shared query Principal -> async ?Account;"));
+    Ok(())
+}
+
+#[test]
+fn test_gen_motoko_bindings_failure_missing_candid() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let canister = RustCanisterCfg::new("ledger");
+    let result = gen_motoko_bindings(
+        temp_dir.path(),
+        "ledger",
+        &canister,
+        Path::new("src/motoko_app/bindings"),
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,60 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+};
+
+use anyhow::Result;
+use candid_gen::{
+    functions::gen_rust_bindings::gen_rust_bindings, types::dfx_cfg::RustCanisterCfg,
+};
+use tempfile::tempdir;
+
+const CANDID: &str = r#"
+    type Account = record { owner : principal; balance : nat };
+    service : {
+        get_account : (principal) -> (opt Account) query;
+        transfer : (principal, nat) -> ();
+    }
+"#;
+
+#[test]
+fn test_gen_rust_bindings_success() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let dependency = RustCanisterCfg::new("ledger-canister");
+    create_dir_all(temp_dir.path().join("src/ledger-canister"))?;
+    write(
+        temp_dir.path().join(&dependency.candid_file_path_str),
+        CANDID,
+    )?;
+
+    gen_rust_bindings(
+        temp_dir.path(),
+        "ledger-canister",
+        &dependency,
+        Path::new("src/wallet/src/clients"),
+    )?;
+
+    let bindings = read_to_string(
+        temp_dir
+            .path()
+            .join("src/wallet/src/clients/ledger_canister.rs"),
+    )?;
+    assert!(bindings.contains("pub struct Account"));
+    assert!(bindings.contains("pub struct LedgerCanister(pub Principal);"));
+    assert!(bindings.contains("pub async fn get_account"));
+    assert!(bindings.contains("pub async fn transfer"));
+    Ok(())
+}
+
+#[test]
+fn test_gen_rust_bindings_failure_missing_candid() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let dependency = RustCanisterCfg::new("ledger");
+    let result = gen_rust_bindings(
+        temp_dir.path(),
+        "ledger",
+        &dependency,
+        Path::new("src/wallet/src/clients"),
+    );
+    assert!(result.is_err());
+}
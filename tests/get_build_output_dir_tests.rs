@@ -0,0 +1,98 @@
+mod common;
+
+use std::{
+    env::{remove_var, set_current_dir, set_var},
+    fs::{create_dir_all, write},
+};
+
+use anyhow::Result;
+use candid_gen::{
+    functions::{
+        get_build_output_dir::get_build_output_dir, get_cargo_metadata::get_cargo_metadata,
+    },
+    types::build_options::BuildOptions,
+    BUILD_PROFILE, BUILD_TARGET,
+};
+use common::create_temp_cargo_package;
+use serial_test::serial;
+use tempfile::tempdir;
+
+#[test]
+#[serial]
+fn test_get_build_output_dir_default() -> Result<()> {
+    remove_var("CARGO_TARGET_DIR");
+    let temp_dir = create_temp_cargo_package("test", "")?;
+    assert_eq!(
+        get_build_output_dir(&get_cargo_metadata(None)?, BUILD_PROFILE),
+        temp_dir
+            .path()
+            .canonicalize()?
+            .join("target")
+            .join(BUILD_TARGET)
+            .join(BUILD_PROFILE)
+    );
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_build_output_dir_cargo_target_dir_env() -> Result<()> {
+    let temp_dir = create_temp_cargo_package("test", "")?;
+    let target_dir = temp_dir.path().join("custom_target");
+    set_var("CARGO_TARGET_DIR", &target_dir);
+    let build_output_dir = get_build_output_dir(&get_cargo_metadata(None)?, BUILD_PROFILE);
+    remove_var("CARGO_TARGET_DIR");
+    assert_eq!(
+        build_output_dir,
+        target_dir.join("wasm32-unknown-unknown/release")
+    );
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_build_output_dir_cargo_config() -> Result<()> {
+    remove_var("CARGO_TARGET_DIR");
+    let temp_dir = create_temp_cargo_package("test", "")?;
+    create_dir_all(temp_dir.path().join(".cargo"))?;
+    write(
+        temp_dir.path().join(".cargo/config.toml"),
+        "[build]\ntarget-dir = \"build\"\n",
+    )?;
+    assert_eq!(
+        get_build_output_dir(&get_cargo_metadata(None)?, BUILD_PROFILE),
+        temp_dir
+            .path()
+            .canonicalize()?
+            .join("build/wasm32-unknown-unknown/release")
+    );
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_build_output_dir_without_cargo_project() -> Result<()> {
+    let temp_dir = tempdir()?;
+    set_current_dir(&temp_dir)?;
+    assert!(get_cargo_metadata(None).is_err());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_build_output_dir_custom_profile() -> Result<()> {
+    remove_var("CARGO_TARGET_DIR");
+    let temp_dir = create_temp_cargo_package("test", "")?;
+    let build_options = BuildOptions {
+        profile: Some("dev".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        get_build_output_dir(&get_cargo_metadata(None)?, build_options.profile_dir()),
+        temp_dir
+            .path()
+            .canonicalize()?
+            .join("target/wasm32-unknown-unknown/debug")
+    );
+    Ok(())
+}
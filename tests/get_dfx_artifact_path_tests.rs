@@ -0,0 +1,46 @@
+use std::{
+    fs::{create_dir_all, write, File},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Result;
+use candid_gen::functions::get_dfx_artifact_path::get_dfx_artifact_path;
+use tempfile::tempdir;
+
+#[test]
+fn test_get_dfx_artifact_path() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let artifact_dir = temp_dir.path().join(".dfx/local/canisters/counter");
+    let wasm_path = artifact_dir.join("counter.wasm");
+    let wasm_gz_path = artifact_dir.join("counter.wasm.gz");
+
+    assert_eq!(
+        get_dfx_artifact_path(temp_dir.path(), "counter", "local"),
+        wasm_path
+    );
+
+    create_dir_all(&artifact_dir)?;
+    write(&wasm_gz_path, "")?;
+    assert_eq!(
+        get_dfx_artifact_path(temp_dir.path(), "counter", "local"),
+        wasm_gz_path
+    );
+
+    write(&wasm_path, "")?;
+    File::options()
+        .write(true)
+        .open(&wasm_gz_path)?
+        .set_modified(SystemTime::now() - Duration::from_secs(60))?;
+    assert_eq!(
+        get_dfx_artifact_path(temp_dir.path(), "counter", "local"),
+        wasm_path
+    );
+
+    assert_eq!(
+        get_dfx_artifact_path(temp_dir.path(), "counter", "ic"),
+        temp_dir
+            .path()
+            .join(".dfx/ic/canisters/counter/counter.wasm")
+    );
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use std::fs::{create_dir_all, write};
+
+use anyhow::Result;
+use candid_gen::functions::get_manifest_path::get_manifest_path;
+use tempfile::tempdir;
+
+#[test]
+fn test_get_manifest_path_parent_workspace() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let workspace_dir = temp_dir.path().canonicalize()?;
+    write(
+        workspace_dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"counter\"]\nresolver = \"2\"\n",
+    )?;
+    create_dir_all(workspace_dir.join("counter/src"))?;
+    write(
+        workspace_dir.join("counter/Cargo.toml"),
+        "[package]\nname = \"counter\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )?;
+    write(workspace_dir.join("counter/src/lib.rs"), "")?;
+    let project_root = workspace_dir.join("ic");
+    create_dir_all(&project_root)?;
+    write(project_root.join("dfx.json"), "{}")?;
+
+    assert_eq!(
+        get_manifest_path(&project_root)?,
+        workspace_dir.join("Cargo.toml")
+    );
+    assert_eq!(
+        get_manifest_path(&workspace_dir.join("counter"))?,
+        workspace_dir.join("Cargo.toml")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_get_manifest_path_failure() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let error = get_manifest_path(temp_dir.path()).unwrap_err();
+    assert!(error.to_string().contains("--manifest-path"));
+    Ok(())
+}
@@ -2,8 +2,8 @@ use anyhow::Result;
 use candid_gen::functions::get_project_root::get_project_root;
 use serial_test::serial;
 use std::{
-    env::{current_dir, set_current_dir, set_var},
-    fs::{create_dir, File},
+    env::{current_dir, remove_var, set_current_dir, set_var, var_os},
+    fs::{create_dir, create_dir_all, File},
     path::{Path, PathBuf},
 };
 use tempfile::{tempdir, TempDir};
@@ -62,20 +62,25 @@ fn test_get_project_root_failure_no_dfx() {
 
 #[test]
 #[serial]
-fn test_get_project_root_failure_no_cargo() {
+fn test_get_project_root_without_cargo() {
     let temp_dir = tempdir().expect("Failed to create temp dir");
     File::create(temp_dir.path().join("dfx.json")).expect("Failed to create dfx.json");
 
     let original_dir = change_dir(&temp_dir).expect("Failed to get current directory");
 
-    let home_dir = temp_dir.path().parent().unwrap();
-    set_var("HOME", home_dir);
+    // The Cargo workspace is located separately, dfx.json is enough to find the project root
+    let original_home = var_os("HOME");
+    remove_var("HOME");
 
     let result = get_project_root();
 
+    // Revert the current directory and HOME
     set_current_dir(original_dir).expect("Failed to revert current directory");
+    if let Some(original_home) = original_home {
+        set_var("HOME", original_home);
+    }
 
-    assert!(result.is_err());
+    assert_eq!(result.unwrap(), temp_dir.path());
 }
 
 #[test]
@@ -117,3 +122,21 @@ fn test_get_project_root_from_sub_directory() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), temp_dir.path());
 }
+
+#[test]
+#[serial]
+fn test_get_project_root_stops_at_vcs_root() {
+    let temp_dir = create_temp_project_with_files();
+    let repo_dir = temp_dir.path().join("repo");
+    let sub_dir = repo_dir.join("subdir");
+    create_dir_all(repo_dir.join(".git")).expect("Failed to create .git");
+    create_dir(&sub_dir).expect("Failed to create subdir");
+
+    let original_dir = change_dir(&sub_dir).expect("Failed to get current directory");
+
+    let result = get_project_root();
+
+    set_current_dir(original_dir).expect("Failed to revert current directory");
+
+    assert!(result.is_err());
+}
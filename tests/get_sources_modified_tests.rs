@@ -0,0 +1,43 @@
+use std::{
+    fs::{create_dir_all, write, File},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Result;
+use candid_gen::functions::get_sources_modified::get_sources_modified;
+use tempfile::tempdir;
+
+fn write_with_modified(path: &Path, modified: SystemTime) -> Result<()> {
+    create_dir_all(path.parent().unwrap())?;
+    write(path, "")?;
+    File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(modified)?;
+    Ok(())
+}
+
+#[test]
+fn test_get_sources_modified() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let package_dir = temp_dir.path().join("counter");
+    let target_dir = temp_dir.path().join("target");
+    let now = SystemTime::now();
+    let an_hour_ago = now - Duration::from_secs(3600);
+    let a_day_ago = now - Duration::from_secs(86400);
+    let watch_paths = vec![package_dir.clone()];
+
+    assert_eq!(get_sources_modified(&watch_paths, &target_dir), None);
+
+    write_with_modified(&package_dir.join("Cargo.toml"), a_day_ago)?;
+    write_with_modified(&package_dir.join("src/lib.rs"), an_hour_ago)?;
+    write_with_modified(&package_dir.join("counter.did"), now)?;
+    write_with_modified(&package_dir.join(".dfx/generated.rs"), now)?;
+    write_with_modified(&package_dir.join("target/debug/generated.rs"), now)?;
+    assert_eq!(
+        get_sources_modified(&watch_paths, &package_dir.join("target")),
+        Some(an_hour_ago)
+    );
+    Ok(())
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use std::env::set_current_dir;
+
+use anyhow::Result;
+use candid_gen::{
+    functions::{get_cargo_metadata::get_cargo_metadata, get_wasm_file_name::get_wasm_file_name},
+    types::dfx_cfg::RustCanisterCfg,
+};
+use common::create_temp_cargo_package;
+use serde_json::json;
+use serial_test::serial;
+use tempfile::tempdir;
+
+#[test]
+#[serial]
+fn test_get_wasm_file_name_hyphenated_package() -> Result<()> {
+    let _temp_dir =
+        create_temp_cargo_package("ledger-canister", "[lib]\ncrate-type = [\"cdylib\"]\n")?;
+    let canister = RustCanisterCfg::new("ledger-canister");
+    assert_eq!(
+        get_wasm_file_name(&canister, Some(&get_cargo_metadata(None)?)),
+        "ledger_canister.wasm"
+    );
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_wasm_file_name_custom_lib_name() -> Result<()> {
+    let _temp_dir = create_temp_cargo_package(
+        "ledger-canister",
+        "[lib]\nname = \"ledger\"\ncrate-type = [\"cdylib\"]\n",
+    )?;
+    let canister = RustCanisterCfg::new("ledger-canister");
+    assert_eq!(
+        get_wasm_file_name(&canister, Some(&get_cargo_metadata(None)?)),
+        "ledger.wasm"
+    );
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_wasm_file_name_dfx_crate_field() -> Result<()> {
+    let canister: RustCanisterCfg = serde_json::from_value(json!({
+        "package": "ledger",
+        "candid": "src/ledger/ledger.did",
+        "type": "rust",
+        "crate": "ledger-impl"
+    }))?;
+    assert_eq!(get_wasm_file_name(&canister, None), "ledger_impl.wasm");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_wasm_file_name_without_cargo_project() -> Result<()> {
+    let temp_dir = tempdir()?;
+    set_current_dir(&temp_dir)?;
+    let canister = RustCanisterCfg::new("my-canister");
+    assert_eq!(
+        get_wasm_file_name(&canister, get_cargo_metadata(None).ok().as_ref()),
+        "my_canister.wasm"
+    );
+    Ok(())
+}
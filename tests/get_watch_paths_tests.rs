@@ -0,0 +1,90 @@
+use std::{
+    env::set_current_dir,
+    fs::{create_dir_all, write},
+    path::Path,
+};
+
+use anyhow::Result;
+use candid_gen::functions::{
+    get_cargo_metadata::get_cargo_metadata,
+    get_watch_paths::{get_watch_paths, is_source_file},
+};
+use serial_test::serial;
+use tempfile::tempdir;
+
+fn create_package(package_dir: &Path, package: &str, dependencies: &str) -> Result<()> {
+    create_dir_all(package_dir.join("src"))?;
+    write(
+        package_dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n{}",
+            package, dependencies
+        ),
+    )?;
+    write(package_dir.join("src/lib.rs"), "")?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_watch_paths() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root_dir = temp_dir.path().canonicalize()?;
+    let workspace_dir = root_dir.join("workspace");
+    let external_dir = root_dir.join("external_lib");
+    create_dir_all(&workspace_dir)?;
+    write(
+        workspace_dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"counter\", \"ledger\", \"shared\"]\nresolver = \"2\"\n",
+    )?;
+    create_package(
+        &workspace_dir.join("counter"),
+        "counter",
+        "shared = { path = \"../shared\" }\n",
+    )?;
+    create_package(&workspace_dir.join("ledger"), "ledger", "")?;
+    create_package(
+        &workspace_dir.join("shared"),
+        "shared",
+        "external_lib = { path = \"../../external_lib\" }\n",
+    )?;
+    create_package(&external_dir, "external_lib", "")?;
+    set_current_dir(&workspace_dir)?;
+
+    let metadata = get_cargo_metadata(None)?;
+    assert_eq!(
+        get_watch_paths("counter", &metadata),
+        vec![
+            external_dir.clone(),
+            workspace_dir.join("counter"),
+            workspace_dir.join("shared"),
+        ]
+    );
+    assert_eq!(
+        get_watch_paths("ledger", &metadata),
+        vec![workspace_dir.join("ledger")]
+    );
+    assert!(get_watch_paths("nonexistent", &metadata).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_is_source_file() {
+    let target_dir = Path::new("/project/target");
+    assert!(is_source_file(
+        Path::new("/project/counter/src/lib.rs"),
+        target_dir
+    ));
+    assert!(is_source_file(
+        Path::new("/project/counter/Cargo.toml"),
+        target_dir
+    ));
+    assert!(!is_source_file(
+        Path::new("/project/counter/counter.did"),
+        target_dir
+    ));
+    assert!(!is_source_file(
+        Path::new("/project/target/debug/build/counter/out/generated.rs"),
+        target_dir
+    ));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use anyhow::Result;
+use candid_gen::functions::read_candid_metadata::read_candid_metadata;
+use common::CANDID;
+
+#[test]
+fn test_read_candid_metadata_public() -> Result<()> {
+    let wasm = wat::parse_str(format!(
+        r#"(module (@custom "icp:public candid:service" "{}"))"#,
+        CANDID
+    ))?;
+    assert_eq!(read_candid_metadata(&wasm)?, Some(CANDID.to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_read_candid_metadata_private() -> Result<()> {
+    let wasm = wat::parse_str(format!(
+        r#"(module (@custom "icp:private candid:service" "{}"))"#,
+        CANDID
+    ))?;
+    assert_eq!(read_candid_metadata(&wasm)?, Some(CANDID.to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_read_candid_metadata_missing() -> Result<()> {
+    let wasm = wat::parse_str(r#"(module (@custom "icp:public other" "data"))"#)?;
+    assert_eq!(read_candid_metadata(&wasm)?, None);
+    Ok(())
+}
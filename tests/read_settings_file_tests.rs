@@ -0,0 +1,81 @@
+use std::fs::write;
+
+use anyhow::Result;
+use candid_gen::{
+    functions::read_settings_file::read_settings_file,
+    types::settings::{SettingSource, SettingsLayer},
+};
+use tempfile::tempdir;
+
+const MANIFEST: &str = r#"
+[workspace]
+members = ["src/*"]
+
+[workspace.metadata.candid-gen]
+profile = "canister"
+features = ["export-candid"]
+"#;
+
+#[test]
+fn test_read_settings_file_from_cargo_manifest() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    write(&manifest_path, MANIFEST)?;
+    assert_eq!(
+        read_settings_file(temp_dir.path(), &manifest_path)?,
+        Some((
+            SettingSource::CargoManifest(manifest_path),
+            SettingsLayer {
+                profile: Some("canister".to_string()),
+                features: Some(vec!["export-candid".to_string()]),
+                ..Default::default()
+            }
+        ))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_settings_file_prefers_candid_gen_toml() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    let settings_path = temp_dir.path().join("candid-gen.toml");
+    write(&manifest_path, MANIFEST)?;
+    write(
+        &settings_path,
+        "candid_path_template = \"candid/{name}.did\"\ndeny_breaking = true\n\n[env]\nRUSTFLAGS = \"-C opt-level=z\"\n",
+    )?;
+    let (source, settings_layer) = read_settings_file(temp_dir.path(), &manifest_path)?.unwrap();
+    assert_eq!(source, SettingSource::File(settings_path));
+    assert_eq!(
+        settings_layer.candid_path_template.as_deref(),
+        Some("candid/{name}.did")
+    );
+    assert_eq!(settings_layer.deny_breaking, Some(true));
+    assert_eq!(settings_layer.env.unwrap()["RUSTFLAGS"], "-C opt-level=z");
+    assert_eq!(settings_layer.profile, None);
+    Ok(())
+}
+
+#[test]
+fn test_read_settings_file_missing() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    write(&manifest_path, "[workspace]\nmembers = [\"src/*\"]\n")?;
+    assert_eq!(read_settings_file(temp_dir.path(), &manifest_path)?, None);
+    Ok(())
+}
+
+#[test]
+fn test_read_settings_file_unknown_key() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    write(&manifest_path, MANIFEST)?;
+    write(
+        temp_dir.path().join("candid-gen.toml"),
+        "profle = \"dev\"\n",
+    )?;
+    let error = read_settings_file(temp_dir.path(), &manifest_path).unwrap_err();
+    assert!(format!("{:#}", error).contains("unknown field `profle`"));
+    Ok(())
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use std::{fs::write, io::Write};
+
+use anyhow::Result;
+use candid_gen::functions::read_wasm_candid::read_wasm_candid;
+use common::{mock_canister_wasm, CANDID};
+use flate2::{write::GzEncoder, Compression};
+use tempfile::tempdir;
+
+#[test]
+fn test_read_wasm_candid_gzipped() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let wasm_gz_path = temp_dir.path().join("test.wasm.gz");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&mock_canister_wasm(CANDID)?)?;
+    write(&wasm_gz_path, encoder.finish()?)?;
+    assert_eq!(read_wasm_candid(&wasm_gz_path, false)?, CANDID);
+    Ok(())
+}
+
+#[test]
+fn test_read_wasm_candid_from_metadata() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let wasm_path = temp_dir.path().join("test.wasm");
+    write(
+        &wasm_path,
+        wat::parse_str(format!(
+            r#"(module (@custom "icp:public candid:service" "{}"))"#,
+            CANDID
+        ))?,
+    )?;
+    assert_eq!(read_wasm_candid(&wasm_path, true)?, CANDID);
+    Ok(())
+}
+
+#[test]
+fn test_read_wasm_candid_failure() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let wasm_path = temp_dir.path().join("test.wasm");
+    write(&wasm_path, wat::parse_str("(module)")?)?;
+    let error = read_wasm_candid(&wasm_path, true).unwrap_err();
+    assert!(format!("{:#}", error).contains("get_candid_pointer"));
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::{fs::write, io::Write};
+
+use anyhow::Result;
+use candid_gen::functions::read_wasm::read_wasm;
+use flate2::{write::GzEncoder, Compression};
+use tempfile::tempdir;
+
+const WASM: &[u8] = b"\0asm\x01\0\0\0";
+
+#[test]
+fn test_read_wasm_plain() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let wasm_path = temp_dir.path().join("test.wasm");
+    write(&wasm_path, WASM)?;
+    assert_eq!(read_wasm(&wasm_path)?, WASM);
+    Ok(())
+}
+
+#[test]
+fn test_read_wasm_gzipped() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let wasm_gz_path = temp_dir.path().join("test.wasm.gz");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(WASM)?;
+    write(&wasm_gz_path, encoder.finish()?)?;
+    assert_eq!(read_wasm(&wasm_gz_path)?, WASM);
+    Ok(())
+}
+
+#[test]
+fn test_read_wasm_missing_file() {
+    let temp_dir = tempdir().unwrap();
+    let result = read_wasm(temp_dir.path().join("missing.wasm"));
+    assert!(format!("{:#}", result.unwrap_err()).contains("Failed to read"));
+}
@@ -27,13 +27,6 @@ fn test_run_command_cargo() {
     assert!(result.unwrap().contains("cargo"));
 }
 
-#[test]
-fn test_run_command_candid_extractor() {
-    let result = run_command("candid-extractor --version");
-    assert!(result.is_ok());
-    assert!(result.unwrap().contains("candid-extractor"));
-}
-
 #[test]
 fn test_run_command_target_wasm32() {
     let result = run_command("rustup target list --installed");
@@ -0,0 +1,32 @@
+mod common;
+
+use std::fs::{read_to_string, write};
+
+use anyhow::Result;
+use candid_gen::functions::update_candid::update_candid;
+use common::CANDID;
+use tempfile::tempdir;
+
+#[test]
+fn test_update_candid_compatible() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let candid_path = temp_dir.path().join("test.did");
+    write(&candid_path, "service : {}")?;
+    update_candid("test", &candid_path.to_string_lossy(), CANDID, true)?;
+    assert_eq!(read_to_string(&candid_path)?, CANDID);
+    Ok(())
+}
+
+#[test]
+fn test_update_candid_deny_breaking() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let candid_path = temp_dir.path().join("test.did");
+    let old_candid = "service : { greet : (text) -> (text) query; inc : () -> () }";
+    write(&candid_path, old_candid)?;
+    assert!(update_candid("test", &candid_path.to_string_lossy(), CANDID, true).is_err());
+    assert_eq!(read_to_string(&candid_path)?, old_candid);
+
+    update_candid("test", &candid_path.to_string_lossy(), CANDID, false)?;
+    assert_eq!(read_to_string(&candid_path)?, CANDID);
+    Ok(())
+}